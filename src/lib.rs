@@ -28,6 +28,7 @@
 //! ```
 
 pub mod vector;
+pub mod quantization;
 pub mod logging;
 
 // Re-export core types
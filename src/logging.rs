@@ -3,8 +3,10 @@
 
 use anyhow::Result;
 use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{Span, Subscriber};
 use tracing_subscriber::{
-    fmt::{self, format::FmtSpan},
+    fmt::{self, format::FmtSpan, MakeWriter},
     layer::SubscriberExt,
     EnvFilter, Registry,
 };
@@ -77,6 +79,20 @@ impl LogConfig {
 
 /// Initialize structured logging with the given configuration
 pub fn init_logging(config: LogConfig) -> Result<()> {
+    let subscriber = build_subscriber(&config, io::stdout)?;
+    tracing::subscriber::set_global_default(subscriber)?;
+
+    Ok(())
+}
+
+/// Build the subscriber for a configuration without installing it
+///
+/// `init_logging` uses stdout; tests can pass an in-memory writer and install
+/// the result with `tracing::subscriber::with_default`.
+pub fn build_subscriber<W>(config: &LogConfig, writer: W) -> Result<Box<dyn Subscriber + Send + Sync>>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
     // Build the filter from config
     let filter = EnvFilter::try_new(&config.level).or_else(|_| EnvFilter::try_new("info"))?;
 
@@ -98,11 +114,9 @@ pub fn init_logging(config: LogConfig) -> Result<()> {
             .with_target(true)
             .with_thread_ids(true)
             .with_thread_names(true)
-            .with_writer(io::stdout);
-
-        let subscriber = Registry::default().with(filter).with(fmt_layer);
+            .with_writer(writer);
 
-        tracing::subscriber::set_global_default(subscriber)?;
+        Ok(Box::new(Registry::default().with(filter).with(fmt_layer)))
     } else {
         // Pretty format for development
         let fmt_layer = fmt::layer()
@@ -110,14 +124,21 @@ pub fn init_logging(config: LogConfig) -> Result<()> {
             .with_span_events(span_events)
             .with_target(true)
             .with_thread_ids(false)
-            .with_writer(io::stdout);
+            .with_writer(writer);
 
-        let subscriber = Registry::default().with(filter).with(fmt_layer);
-
-        tracing::subscriber::set_global_default(subscriber)?;
+        Ok(Box::new(Registry::default().with(filter).with(fmt_layer)))
     }
+}
 
-    Ok(())
+/// Create a span for one client request
+///
+/// Assigns a process-unique `request_id` so every event logged while the
+/// span is entered (e.g. around SQL execution) can be correlated.
+pub fn request_span(query_type: &str) -> Span {
+    static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+    let request_id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+    tracing::info_span!("request", request_id, query_type)
 }
 
 /// Initialize logging from environment variables
@@ -197,4 +218,78 @@ mod tests {
         // That's okay - we just want to verify the API works
         let _ = init_logging(config);
     }
+
+    /// In-memory writer for capturing log output
+    #[derive(Clone, Default)]
+    struct CaptureWriter(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl io::Write for CaptureWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'w> MakeWriter<'w> for CaptureWriter {
+        type Writer = CaptureWriter;
+
+        fn make_writer(&'w self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    fn capture(json_format: bool) -> String {
+        let config = LogConfig {
+            level: "info".to_string(),
+            json_format,
+            log_queries: false,
+            log_spans: false,
+            log_file: None,
+        };
+        let writer = CaptureWriter::default();
+        let subscriber = build_subscriber(&config, writer.clone()).unwrap();
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = request_span("SELECT").entered();
+            tracing::info!("executing query");
+        });
+
+        let output = writer.0.lock().unwrap().clone();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_json_toggle_changes_format() {
+        let json = capture(true);
+        let pretty = capture(false);
+
+        // JSON: one object per line, span fields under "span"
+        let line: serde_json::Value = serde_json::from_str(json.lines().next().unwrap()).unwrap();
+        assert_eq!(line["fields"]["message"], "executing query");
+        assert_eq!(line["span"]["name"], "request");
+        assert_eq!(line["span"]["query_type"], "SELECT");
+        assert!(line["span"]["request_id"].as_u64().unwrap() > 0);
+
+        // Pretty: human-readable, not JSON, still carries the span fields
+        assert!(serde_json::from_str::<serde_json::Value>(pretty.lines().next().unwrap()).is_err());
+        assert!(pretty.contains("executing query"));
+        assert!(pretty.contains("request_id"));
+        assert!(pretty.contains("SELECT"));
+    }
+
+    #[test]
+    fn test_request_span_ids_are_unique() {
+        let request_id = |output: String| {
+            let line: serde_json::Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+            line["span"]["request_id"].as_u64().unwrap()
+        };
+
+        let first = request_id(capture(true));
+        let second = request_id(capture(true));
+        assert!(second > first);
+    }
 }
@@ -0,0 +1,13 @@
+//! Binary quantization
+//!
+//! RaBitQ-style 1-bit quantization: each dimension becomes one bit, and
+//! vectors are compared with popcount-based Hamming distance. Used as a fast,
+//! low-memory first stage before reranking with full precision vectors.
+
+pub mod quantized_vector;
+pub mod quantization_model;
+pub mod quantized_store;
+
+pub use quantized_vector::QuantizedVector;
+pub use quantization_model::QuantizationModel;
+pub use quantized_store::QuantizedVectorStore;
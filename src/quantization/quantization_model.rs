@@ -0,0 +1,312 @@
+//! Training and applying binary quantization
+//!
+//! A model holds per-dimension thresholds fit to a representative sample.
+//! Each dimension is quantized to 1 if the value is >= its threshold.
+//! Centering on the sample mean keeps bits balanced for data that isn't
+//! zero-mean, which is what makes Hamming distance track L2.
+
+use super::quantized_vector::QuantizedVector;
+use anyhow::Result;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Seed for the optional random rotation (training stays deterministic)
+const ROTATION_SEED: u64 = 42;
+
+/// Binary quantization model
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QuantizationModel {
+    /// Vector dimensionality
+    dimensions: usize,
+
+    /// Per-dimension thresholds (sample mean, in rotated space)
+    thresholds: Vec<f32>,
+
+    /// Per-dimension reconstruction offset: mean |x - threshold|
+    scales: Vec<f32>,
+
+    /// Optional random rotation applied before thresholding
+    rotation: Option<Vec<GivensRotation>>,
+}
+
+/// Rotation in the plane of two dimensions
+///
+/// A sequence of these forms a random orthogonal transform that costs
+/// O(rotations) per vector instead of O(D^2) for a dense matrix.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+struct GivensRotation {
+    i: u32,
+    j: u32,
+    cos: f32,
+    sin: f32,
+}
+
+impl QuantizationModel {
+    /// Untrained model: zero thresholds, unit scales, no rotation
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions,
+            thresholds: vec![0.0; dimensions],
+            scales: vec![1.0; dimensions],
+            rotation: None,
+        }
+    }
+
+    /// Fit thresholds to a representative sample
+    ///
+    /// Thresholds are the per-dimension means, computed after the random
+    /// rotation when `randomize` is set. Dimensions that are constant in the
+    /// sample (including any sample of a single vector) fall back to a zero
+    /// threshold.
+    ///
+    /// Fails on an empty sample, mismatched dimensions or NaN/Inf values.
+    pub fn train(sample: &[Vec<f32>], randomize: bool) -> Result<Self> {
+        let Some(first) = sample.first() else {
+            anyhow::bail!("Cannot train quantization model on empty sample");
+        };
+        let dimensions = first.len();
+
+        for (i, vector) in sample.iter().enumerate() {
+            if vector.len() != dimensions {
+                anyhow::bail!(
+                    "Sample vector {} dimension mismatch: expected {}, got {}",
+                    i,
+                    dimensions,
+                    vector.len()
+                );
+            }
+            if vector.iter().any(|x| !x.is_finite()) {
+                anyhow::bail!("Sample vector {} contains NaN or Infinity", i);
+            }
+        }
+
+        let mut model = Self::new(dimensions);
+        if randomize {
+            model.rotation = Some(random_rotation(dimensions));
+        }
+
+        let rotated: Vec<Vec<f32>> = sample.iter().map(|v| model.rotate(v)).collect();
+        let n = rotated.len() as f32;
+
+        for dim in 0..dimensions {
+            let mean = rotated.iter().map(|v| v[dim]).sum::<f32>() / n;
+            let constant = rotated.iter().all(|v| v[dim] == rotated[0][dim]);
+            let threshold = if constant { 0.0 } else { mean };
+
+            let scale = rotated.iter().map(|v| (v[dim] - threshold).abs()).sum::<f32>() / n;
+
+            model.thresholds[dim] = threshold;
+            model.scales[dim] = if scale > 0.0 { scale } else { 1.0 };
+        }
+
+        Ok(model)
+    }
+
+    /// Quantize a vector to 1 bit per dimension
+    pub fn quantize(&self, vector: &[f32]) -> Result<QuantizedVector> {
+        self.check_dimensions(vector.len())?;
+
+        let rotated = self.rotate(vector);
+        let bits: Vec<bool> = rotated
+            .iter()
+            .zip(&self.thresholds)
+            .map(|(&x, &t)| x >= t)
+            .collect();
+
+        Ok(QuantizedVector::from_bools(&bits))
+    }
+
+    /// Approximate reconstruction of a quantized vector
+    ///
+    /// Each dimension becomes threshold ± scale, then the rotation is undone.
+    pub fn dequantize(&self, quantized: &QuantizedVector) -> Result<Vec<f32>> {
+        self.check_dimensions(quantized.dimensions())?;
+
+        let mut values: Vec<f32> = (0..self.dimensions)
+            .map(|dim| {
+                let offset = if quantized.get_bit(dim) { self.scales[dim] } else { -self.scales[dim] };
+                self.thresholds[dim] + offset
+            })
+            .collect();
+
+        if let Some(ref rotation) = self.rotation {
+            for r in rotation.iter().rev() {
+                r.apply(&mut values, -r.sin);
+            }
+        }
+
+        Ok(values)
+    }
+
+    /// Vector dimensionality
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Per-dimension thresholds (in rotated space when randomized)
+    pub fn thresholds(&self) -> &[f32] {
+        &self.thresholds
+    }
+
+    /// Whether a random rotation is applied before thresholding
+    pub fn is_randomized(&self) -> bool {
+        self.rotation.is_some()
+    }
+
+    fn rotate(&self, vector: &[f32]) -> Vec<f32> {
+        let mut values = vector.to_vec();
+        if let Some(ref rotation) = self.rotation {
+            for r in rotation {
+                r.apply(&mut values, r.sin);
+            }
+        }
+        values
+    }
+
+    fn check_dimensions(&self, actual: usize) -> Result<()> {
+        if actual != self.dimensions {
+            anyhow::bail!(
+                "Vector dimension mismatch: expected {}, got {}",
+                self.dimensions,
+                actual
+            );
+        }
+        Ok(())
+    }
+}
+
+impl GivensRotation {
+    /// Rotate dimensions (i, j); pass `-sin` for the inverse
+    #[inline]
+    fn apply(&self, values: &mut [f32], sin: f32) {
+        let (i, j) = (self.i as usize, self.j as usize);
+        let (a, b) = (values[i], values[j]);
+        values[i] = self.cos * a - sin * b;
+        values[j] = sin * a + self.cos * b;
+    }
+}
+
+/// Random orthogonal transform as D * ceil(log2 D) Givens rotations
+fn random_rotation(dimensions: usize) -> Vec<GivensRotation> {
+    if dimensions < 2 {
+        return Vec::new();
+    }
+
+    let mut rng = StdRng::seed_from_u64(ROTATION_SEED);
+    let rounds = (usize::BITS - (dimensions - 1).leading_zeros()) as usize;
+
+    (0..dimensions * rounds)
+        .map(|_| {
+            let i = rng.gen_range(0..dimensions);
+            let j = (i + rng.gen_range(1..dimensions)) % dimensions;
+            let angle: f32 = rng.gen_range(0.0..std::f32::consts::TAU);
+            GivensRotation {
+                i: i as u32,
+                j: j as u32,
+                cos: angle.cos(),
+                sin: angle.sin(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reconstruction_error(model: &QuantizationModel, vectors: &[Vec<f32>]) -> f32 {
+        vectors
+            .iter()
+            .map(|v| {
+                let restored = model.dequantize(&model.quantize(v).unwrap()).unwrap();
+                v.iter().zip(&restored).map(|(a, b)| (a - b) * (a - b)).sum::<f32>()
+            })
+            .sum::<f32>()
+            / vectors.len() as f32
+    }
+
+    fn offset_vectors(count: usize, dimensions: usize) -> Vec<Vec<f32>> {
+        let mut rng = StdRng::seed_from_u64(7);
+        (0..count)
+            .map(|_| {
+                (0..dimensions)
+                    .map(|d| 5.0 + d as f32 * 0.1 + rng.gen_range(-1.0..1.0))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_trained_model_lowers_reconstruction_error() {
+        let vectors = offset_vectors(500, 32);
+        let untrained = reconstruction_error(&QuantizationModel::new(32), &vectors);
+
+        for randomize in [false, true] {
+            let model = QuantizationModel::train(&vectors, randomize).unwrap();
+            assert_eq!(model.is_randomized(), randomize);
+
+            // Thresholds are centered on the data, not on zero
+            let mean_threshold = model.thresholds().iter().sum::<f32>() / 32.0;
+            if !randomize {
+                assert!((mean_threshold - 6.55).abs() < 0.1);
+            }
+
+            let trained = reconstruction_error(&model, &vectors);
+            assert!(
+                trained < untrained / 10.0,
+                "trained error {} not below untrained {}",
+                trained,
+                untrained
+            );
+        }
+    }
+
+    #[test]
+    fn test_rotation_is_orthogonal() {
+        let model = QuantizationModel::train(&offset_vectors(10, 50), true).unwrap();
+        let v: Vec<f32> = (0..50).map(|i| i as f32 - 20.0).collect();
+
+        let rotated = model.rotate(&v);
+        let norm = |x: &[f32]| x.iter().map(|a| a * a).sum::<f32>().sqrt();
+        assert!((norm(&rotated) - norm(&v)).abs() < 1e-3);
+
+        let mut restored = rotated;
+        for r in model.rotation.as_ref().unwrap().iter().rev() {
+            r.apply(&mut restored, -r.sin);
+        }
+        for (a, b) in v.iter().zip(&restored) {
+            assert!((a - b).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_tiny_or_constant_sample_falls_back_to_zero() {
+        // Single vector
+        let model = QuantizationModel::train(&[vec![3.0, -2.0, 1.0]], false).unwrap();
+        assert_eq!(model.thresholds(), &[0.0, 0.0, 0.0]);
+
+        // Constant sample; bits still carry the sign
+        let model = QuantizationModel::train(&vec![vec![3.0, -2.0]; 10], false).unwrap();
+        assert_eq!(model.thresholds(), &[0.0, 0.0]);
+        let q = model.quantize(&[3.0, -2.0]).unwrap();
+        assert!(q.get_bit(0));
+        assert!(!q.get_bit(1));
+        assert_eq!(model.dequantize(&q).unwrap(), vec![3.0, -2.0]);
+
+        // Only the constant dimension falls back
+        let model = QuantizationModel::train(&[vec![1.0, 4.0], vec![3.0, 4.0]], false).unwrap();
+        assert_eq!(model.thresholds(), &[2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_train_invalid_sample() {
+        assert!(QuantizationModel::train(&[], false).is_err());
+        assert!(QuantizationModel::train(&[vec![1.0, 2.0], vec![1.0]], false).is_err());
+        assert!(QuantizationModel::train(&[vec![1.0, f32::NAN]], false).is_err());
+
+        let model = QuantizationModel::new(4);
+        assert!(model.quantize(&[1.0; 3]).is_err());
+        assert!(model.dequantize(&QuantizedVector::from_bools(&[true; 5])).is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! Hamming-distance KNN over binary quantized vectors
+//!
+//! Flat scan in the binary domain, without an HNSW graph. Stores only the
+//! packed bits (dimensions / 8 bytes per vector), so it fits datasets that
+//! are too large for full precision storage. Results are approximate and
+//! meant to be reranked with exact distances.
+
+use super::quantized_vector::{hamming_words, QuantizedVector};
+use anyhow::Result;
+use std::collections::BinaryHeap;
+
+/// Store of binary quantized vectors searched by Hamming distance
+#[derive(Clone, Debug)]
+pub struct QuantizedVectorStore {
+    /// Packed bits of all vectors, `words_per_vector` words each
+    bits: Vec<u64>,
+
+    /// u64 words per vector
+    words_per_vector: usize,
+
+    /// Vector dimensionality
+    dimensions: usize,
+
+    /// Number of stored vectors
+    len: usize,
+}
+
+impl QuantizedVectorStore {
+    /// Create empty store
+    pub fn new(dimensions: usize) -> Self {
+        Self {
+            bits: Vec::new(),
+            words_per_vector: QuantizedVector::words_for(dimensions),
+            dimensions,
+            len: 0,
+        }
+    }
+
+    /// Create empty store with room for `capacity` vectors
+    pub fn with_capacity(dimensions: usize, capacity: usize) -> Self {
+        let mut store = Self::new(dimensions);
+        store.bits.reserve(capacity * store.words_per_vector);
+        store
+    }
+
+    /// Insert a quantized vector and return its ID
+    pub fn insert(&mut self, vector: QuantizedVector) -> Result<usize> {
+        self.check_dimensions(&vector)?;
+
+        self.bits.extend_from_slice(vector.bits());
+        self.len += 1;
+        Ok(self.len - 1)
+    }
+
+    /// Get the packed bits of a stored vector
+    pub fn get(&self, id: usize) -> Option<QuantizedVector> {
+        if id >= self.len {
+            return None;
+        }
+        Some(QuantizedVector::new(self.words(id).to_vec(), self.dimensions))
+    }
+
+    /// K nearest neighbors by Hamming distance
+    ///
+    /// Scans every stored vector with popcount. Returns (id, distance)
+    /// pairs sorted by distance, ties broken by lower ID.
+    pub fn knn_hamming(&self, query_bits: &QuantizedVector, k: usize) -> Result<Vec<(usize, u32)>> {
+        self.check_dimensions(query_bits)?;
+
+        if k == 0 || self.len == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Max-heap of the k best so far; root is the worst kept candidate
+        let mut heap: BinaryHeap<(u32, usize)> = BinaryHeap::with_capacity(k + 1);
+        for id in 0..self.len {
+            let dist = hamming_words(query_bits.bits(), self.words(id));
+            if heap.len() < k {
+                heap.push((dist, id));
+            } else if let Some(&worst) = heap.peek() {
+                if (dist, id) < worst {
+                    heap.pop();
+                    heap.push((dist, id));
+                }
+            }
+        }
+
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|(dist, id)| (id, dist))
+            .collect())
+    }
+
+    /// Number of stored vectors
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if store is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Vector dimensionality
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Memory used by the packed bits in bytes
+    pub fn memory_usage(&self) -> usize {
+        self.bits.len() * std::mem::size_of::<u64>()
+    }
+
+    fn words(&self, id: usize) -> &[u64] {
+        let start = id * self.words_per_vector;
+        &self.bits[start..start + self.words_per_vector]
+    }
+
+    fn check_dimensions(&self, vector: &QuantizedVector) -> Result<()> {
+        if vector.dimensions() != self.dimensions {
+            anyhow::bail!(
+                "Vector dimension mismatch: expected {}, got {}",
+                self.dimensions,
+                vector.dimensions()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_quantized(rng: &mut StdRng, dimensions: usize) -> QuantizedVector {
+        let values: Vec<bool> = (0..dimensions).map(|_| rng.gen_bool(0.5)).collect();
+        QuantizedVector::from_bools(&values)
+    }
+
+    #[test]
+    fn test_knn_hamming_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let dimensions = 100; // Not a multiple of 64
+
+        let vectors: Vec<QuantizedVector> =
+            (0..500).map(|_| random_quantized(&mut rng, dimensions)).collect();
+
+        let mut store = QuantizedVectorStore::with_capacity(dimensions, vectors.len());
+        for (i, v) in vectors.iter().enumerate() {
+            assert_eq!(store.insert(v.clone()).unwrap(), i);
+        }
+        assert_eq!(store.len(), 500);
+        assert_eq!(store.memory_usage(), 500 * 16);
+        assert_eq!(store.get(7).as_ref(), Some(&vectors[7]));
+
+        for _ in 0..20 {
+            let query = random_quantized(&mut rng, dimensions);
+
+            // Brute-force scan, same (distance, id) ordering
+            let mut expected: Vec<(usize, u32)> = vectors
+                .iter()
+                .enumerate()
+                .map(|(id, v)| (id, query.hamming_distance(v)))
+                .collect();
+            expected.sort_by_key(|&(id, dist)| (dist, id));
+
+            for k in [1, 10, 50] {
+                let results = store.knn_hamming(&query, k).unwrap();
+                assert_eq!(results, expected[..k]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_knn_hamming_edge_cases() {
+        let mut store = QuantizedVectorStore::new(8);
+        let query = QuantizedVector::from_bools(&[true; 8]);
+
+        // Empty store
+        assert!(store.knn_hamming(&query, 5).unwrap().is_empty());
+
+        store.insert(QuantizedVector::from_bools(&[false; 8])).unwrap();
+        store.insert(query.clone()).unwrap();
+
+        // k = 0, and k larger than the store
+        assert!(store.knn_hamming(&query, 0).unwrap().is_empty());
+        assert_eq!(store.knn_hamming(&query, 10).unwrap(), vec![(1, 0), (0, 8)]);
+
+        // Dimension mismatch
+        let wrong = QuantizedVector::from_bools(&[true; 16]);
+        assert!(store.insert(wrong.clone()).is_err());
+        assert!(store.knn_hamming(&wrong, 1).is_err());
+    }
+}
@@ -0,0 +1,128 @@
+//! Bit-packed binary quantized vector
+
+use serde::{Deserialize, Serialize};
+
+/// Binary quantized vector (1 bit per dimension)
+///
+/// Bits are packed into u64 words, least significant bit first.
+/// Unused bits in the last word are always zero.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuantizedVector {
+    bits: Vec<u64>,
+    dimensions: usize,
+}
+
+impl QuantizedVector {
+    /// Number of u64 words needed for `dimensions` bits
+    pub fn words_for(dimensions: usize) -> usize {
+        dimensions.div_ceil(64)
+    }
+
+    /// Create from packed words
+    ///
+    /// # Panics
+    /// Panics if `bits` has the wrong number of words for `dimensions`.
+    pub fn new(mut bits: Vec<u64>, dimensions: usize) -> Self {
+        assert_eq!(
+            bits.len(),
+            Self::words_for(dimensions),
+            "Packed bits don't match {} dimensions",
+            dimensions
+        );
+
+        // Keep padding bits zero so Hamming distance ignores them
+        let tail = dimensions % 64;
+        if tail != 0 {
+            if let Some(last) = bits.last_mut() {
+                *last &= (1u64 << tail) - 1;
+            }
+        }
+
+        Self { bits, dimensions }
+    }
+
+    /// Create from one bool per dimension
+    pub fn from_bools(values: &[bool]) -> Self {
+        let mut bits = vec![0u64; Self::words_for(values.len())];
+        for (i, _) in values.iter().enumerate().filter(|(_, &b)| b) {
+            bits[i / 64] |= 1u64 << (i % 64);
+        }
+        Self {
+            bits,
+            dimensions: values.len(),
+        }
+    }
+
+    /// Get the bit for a dimension
+    pub fn get_bit(&self, dim: usize) -> bool {
+        debug_assert!(dim < self.dimensions);
+        (self.bits[dim / 64] >> (dim % 64)) & 1 == 1
+    }
+
+    /// Packed words
+    pub fn bits(&self) -> &[u64] {
+        &self.bits
+    }
+
+    /// Number of dimensions
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Hamming distance (number of differing bits)
+    ///
+    /// Both vectors must have the same dimensions.
+    #[inline]
+    pub fn hamming_distance(&self, other: &QuantizedVector) -> u32 {
+        debug_assert_eq!(self.dimensions, other.dimensions);
+        hamming_words(&self.bits, &other.bits)
+    }
+
+    /// Memory used by the packed bits in bytes
+    pub fn memory_size(&self) -> usize {
+        self.bits.len() * std::mem::size_of::<u64>()
+    }
+}
+
+/// Hamming distance between two packed bit slices
+#[inline]
+pub(crate) fn hamming_words(a: &[u64], b: &[u64]) -> u32 {
+    a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_bools_roundtrip() {
+        let values: Vec<bool> = (0..130).map(|i| i % 3 == 0).collect();
+        let q = QuantizedVector::from_bools(&values);
+
+        assert_eq!(q.dimensions(), 130);
+        assert_eq!(q.bits().len(), 3);
+        for (i, &v) in values.iter().enumerate() {
+            assert_eq!(q.get_bit(i), v);
+        }
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        let a = QuantizedVector::from_bools(&[true, false, true, true]);
+        let b = QuantizedVector::from_bools(&[false, false, true, false]);
+
+        assert_eq!(a.hamming_distance(&a), 0);
+        assert_eq!(a.hamming_distance(&b), 2);
+        assert_eq!(b.hamming_distance(&a), 2);
+    }
+
+    #[test]
+    fn test_new_clears_padding_bits() {
+        let a = QuantizedVector::new(vec![u64::MAX], 3);
+        let b = QuantizedVector::from_bools(&[true, true, true]);
+
+        assert_eq!(a, b);
+        assert_eq!(a.hamming_distance(&b), 0);
+        assert_eq!(a.memory_size(), 8);
+    }
+}
@@ -53,6 +53,25 @@ pub struct IndexStats {
     pub quantization_enabled: bool,
 }
 
+/// Pending insert produced by `HNSWIndex::plan_insert`
+///
+/// Holds the vector, its level and the neighbors selected at each level.
+/// Valid only for the index state it was planned against.
+#[derive(Clone, Debug)]
+pub(crate) struct InsertPlan {
+    vector: Vec<f32>,
+    level: u8,
+
+    /// RNG state after drawing `level`
+    rng_state: u64,
+
+    /// Index length when the plan was made
+    base_len: usize,
+
+    /// Selected neighbors, indexed by level
+    neighbors: Vec<Vec<u32>>,
+}
+
 /// HNSW Index
 ///
 /// Hierarchical graph index for approximate nearest neighbor search.
@@ -135,21 +154,23 @@ impl HNSWIndex {
 
     /// Get neighbor count for a node at a level
     pub fn neighbor_count(&self, node_id: u32, level: u8) -> usize {
-        self.neighbors.get_neighbors(node_id, level).len()
+        self.neighbors.neighbor_count(node_id, level)
     }
 
-    /// Assign random level to new node
+    /// Draw a random level for a new node without advancing the RNG
     ///
     /// Uses exponential decay: P(level = l) = (1/M)^l
     /// This ensures most nodes are at level 0, fewer at higher levels.
-    fn random_level(&mut self) -> u8 {
+    ///
+    /// Returns the level and the RNG state to store once it is consumed.
+    fn next_level(&self) -> (u8, u64) {
         // Simple LCG for deterministic random numbers
-        self.rng_state = self.rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
-        let rand_val = (self.rng_state >> 32) as f32 / u32::MAX as f32;
+        let rng_state = self.rng_state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        let rand_val = (rng_state >> 32) as f32 / u32::MAX as f32;
 
         // Exponential distribution: -ln(uniform) / ln(M)
         let level = (-rand_val.ln() * self.params.ml) as u8;
-        level.min(self.params.max_level - 1)
+        (level.min(self.params.max_level - 1), rng_state)
     }
 
     /// Compute distance between two vectors
@@ -170,6 +191,20 @@ impl HNSWIndex {
     /// Returns the node ID assigned to this vector.
     #[instrument(skip(self, vector), fields(dimensions = vector.len(), index_size = self.len()))]
     pub fn insert(&mut self, vector: Vec<f32>) -> Result<u32> {
+        let plan = self.plan_insert(vector)?;
+        self.commit_insert(plan)
+    }
+
+    /// Plan an insert without modifying the index
+    ///
+    /// Validates the vector, draws its level and selects its neighbors at
+    /// every level. Only needs `&self`, so it can run under a shared lock
+    /// alongside searches; `commit_insert` then applies the plan.
+    ///
+    /// The plan is only valid until the index is next modified. Crate-only:
+    /// `ConcurrentHNSWIndex` holds its insert lock from plan to commit, which
+    /// is what keeps other mutations out in between.
+    pub(crate) fn plan_insert(&self, vector: Vec<f32>) -> Result<InsertPlan> {
         // Validate dimensions
         if vector.len() != self.dimensions() {
             error!(
@@ -189,14 +224,50 @@ impl HNSWIndex {
             return Err(HNSWError::InvalidVector);
         }
 
+        // Assign random level
+        let (level, rng_state) = self.next_level();
+
+        // First node has no neighbors to select
+        let neighbors = match self.entry_point {
+            Some(entry_point) => {
+                self.select_insert_neighbors(self.len() as u32, entry_point, &vector, level)?
+            }
+            None => Vec::new(),
+        };
+
+        Ok(InsertPlan {
+            vector,
+            level,
+            rng_state,
+            base_len: self.len(),
+            neighbors,
+        })
+    }
+
+    /// Apply a plan from `plan_insert`
+    ///
+    /// Stores the vector and links the node in one `&mut self` call, so a
+    /// reader behind a lock never sees a partially linked node. Fails if the
+    /// index length changed since the plan was made; other modifications
+    /// (e.g. `optimize_cache_locality`) are not detected, so callers must
+    /// keep them out between plan and commit.
+    pub(crate) fn commit_insert(&mut self, plan: InsertPlan) -> Result<u32> {
+        if plan.base_len != self.len() {
+            return Err(HNSWError::internal(format!(
+                "Stale insert plan: planned against {} nodes, index has {}",
+                plan.base_len,
+                self.len()
+            )));
+        }
+
+        let InsertPlan { vector, level, rng_state, neighbors, .. } = plan;
+
         // Store vector and get ID
-        let node_id = self.vectors.insert(vector.clone()).map_err(|e| {
+        let node_id = self.vectors.insert(vector).map_err(|e| {
             error!(error = ?e, "Failed to store vector");
             HNSWError::Storage(format!("{}", e))
         })?;
-
-        // Assign random level
-        let level = self.random_level();
+        self.rng_state = rng_state;
 
         // Create node
         let node = HNSWNode::new(node_id, level);
@@ -209,7 +280,7 @@ impl HNSWIndex {
         }
 
         // Insert into graph
-        self.insert_into_graph(node_id, &vector, level)?;
+        self.link_into_graph(node_id, &neighbors)?;
 
         // Update entry point if this node has higher level than current entry point
         let entry_point_id = self.entry_point.ok_or_else(|| HNSWError::internal("Entry point should exist after first insert"))?;
@@ -235,16 +306,17 @@ impl HNSWIndex {
         Ok(node_id)
     }
 
-    /// Insert node into graph structure
+    /// Select neighbors for a new node at levels 0..=level
     ///
-    /// Implements HNSW insertion algorithm (Malkov & Yashunin 2018)
-    fn insert_into_graph(
-        &mut self,
+    /// Implements the search half of HNSW insertion (Malkov & Yashunin 2018).
+    /// Returns the selected neighbors indexed by level.
+    fn select_insert_neighbors(
+        &self,
         node_id: u32,
+        entry_point: u32,
         vector: &[f32],
         level: u8,
-    ) -> Result<()> {
-        let entry_point = self.entry_point.ok_or(HNSWError::EmptyIndex)?;
+    ) -> Result<Vec<Vec<u32>>> {
         let entry_level = self.nodes[entry_point as usize].level;
 
         // Search for nearest neighbors at each level above target level
@@ -253,22 +325,33 @@ impl HNSWIndex {
             nearest = self.search_layer(vector, &nearest, 1, lc)?;
         }
 
-        // Insert at levels 0..=level (iterate from top to bottom)
+        // Select at levels 0..=level (iterate from top to bottom)
+        let mut selected = vec![Vec::new(); level as usize + 1];
         for lc in (0..=level).rev() {
             // Find ef_construction nearest neighbors at this level
             let candidates = self.search_layer(vector, &nearest, self.params.ef_construction, lc)?;
 
             // Select M best neighbors using heuristic
-            let m = if lc == 0 {
-                self.params.m * 2 // Level 0 has more connections
-            } else {
-                self.params.m
-            };
+            selected[lc as usize] =
+                self.select_neighbors_heuristic(node_id, &candidates, self.max_neighbors(lc), lc, vector)?;
 
-            let neighbors = self.select_neighbors_heuristic(node_id, &candidates, m, lc, vector)?;
+            // Update nearest for next level
+            nearest = candidates;
+        }
+
+        Ok(selected)
+    }
+
+    /// Link a new node to its selected neighbors
+    ///
+    /// Adds bidirectional links and prunes neighbors that exceed M.
+    fn link_into_graph(&mut self, node_id: u32, neighbors_by_level: &[Vec<u32>]) -> Result<()> {
+        for (lc, neighbors) in neighbors_by_level.iter().enumerate().rev() {
+            let lc = lc as u8;
+            let m = self.max_neighbors(lc);
 
             // Add bidirectional links
-            for &neighbor_id in &neighbors {
+            for &neighbor_id in neighbors {
                 self.neighbors.add_bidirectional_link(node_id, neighbor_id, lc);
             }
 
@@ -276,8 +359,8 @@ impl HNSWIndex {
             self.nodes[node_id as usize].set_neighbor_count(lc, neighbors.len());
 
             // Prune neighbors' connections if they exceed M
-            for &neighbor_id in &neighbors {
-                let neighbor_neighbors = self.neighbors.get_neighbors(neighbor_id, lc).to_vec();
+            for &neighbor_id in neighbors {
+                let neighbor_neighbors = self.neighbors.neighbors(neighbor_id, lc).into_owned();
                 if neighbor_neighbors.len() > m {
                     let neighbor_vec = self.vectors.get(neighbor_id).ok_or(HNSWError::VectorNotFound(neighbor_id))?;
                     let pruned = self.select_neighbors_heuristic(
@@ -293,14 +376,20 @@ impl HNSWIndex {
                     self.nodes[neighbor_id as usize].set_neighbor_count(lc, pruned.len());
                 }
             }
-
-            // Update nearest for next level
-            nearest = candidates;
         }
 
         Ok(())
     }
 
+    /// Maximum neighbors per node at a level
+    fn max_neighbors(&self, level: u8) -> usize {
+        if level == 0 {
+            self.params.m * 2 // Level 0 has more connections
+        } else {
+            self.params.m
+        }
+    }
+
     /// Select neighbors using heuristic (diverse neighbors, better recall)
     ///
     /// Algorithm from Malkov 2018, Section 4
@@ -459,6 +548,7 @@ impl HNSWIndex {
             let visited = &mut buffers.visited;
             let candidates = &mut buffers.candidates;
             let working = &mut buffers.working;
+            let neighbor_ids = &mut buffers.neighbor_ids;
 
             // Initialize with entry points
             for &ep in entry_points {
@@ -480,7 +570,7 @@ impl HNSWIndex {
             }
 
             // Explore neighbors
-            let neighbors = self.neighbors.get_neighbors(current.node_id, level);
+            let neighbors = self.neighbors.neighbors_buffered(current.node_id, level, neighbor_ids);
             for &neighbor_id in neighbors {
                 if visited.contains(&neighbor_id) {
                     continue;
@@ -543,7 +633,7 @@ impl HNSWIndex {
         let mut total_neighbors = 0;
         let mut max_neighbors = 0;
         for node in &self.nodes {
-            let neighbor_count = self.neighbors.get_neighbors(node.id, 0).len();
+            let neighbor_count = self.neighbors.neighbor_count(node.id, 0);
             total_neighbors += neighbor_count;
             max_neighbors = max_neighbors.max(neighbor_count);
         }
@@ -641,6 +731,29 @@ impl HNSWIndex {
         Ok(self.nodes.len())
     }
 
+    /// Delta-encode neighbor lists to reduce graph memory
+    ///
+    /// Search results are unchanged; neighbors are decoded on traversal.
+    /// Best applied after bulk construction: inserting afterwards decompresses
+    /// the lists again. Saved files always use the uncompressed layout.
+    ///
+    /// Returns neighbor list memory (bytes) before and after.
+    #[instrument(skip(self), fields(num_nodes = self.len()))]
+    pub fn compress_neighbors(&mut self) -> (usize, usize) {
+        let before = self.neighbors.memory_usage();
+        self.neighbors.compress();
+        let after = self.neighbors.memory_usage();
+
+        info!(before_bytes = before, after_bytes = after, "Neighbor lists compressed");
+
+        (before, after)
+    }
+
+    /// Whether neighbor lists are currently delta-encoded
+    pub fn neighbors_compressed(&self) -> bool {
+        self.neighbors.is_compressed()
+    }
+
     /// Save index to disk
     ///
     /// Format:
@@ -839,6 +952,7 @@ impl HNSWIndex {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::vector::test_utils::random_vectors;
 
     #[test]
     fn test_hnsw_index_creation() {
@@ -917,6 +1031,32 @@ mod tests {
         assert!(results[0].distance < 0.01); // Should be ~0 (same vector)
     }
 
+    #[test]
+    fn test_plan_commit_insert() {
+        let mut index = HNSWIndex::new(4, HNSWParams::default(), DistanceFunction::L2, false).unwrap();
+        for i in 0..20 {
+            index.insert(vec![i as f32, 0.0, 0.0, 0.0]).unwrap();
+        }
+
+        // Planning leaves the index untouched
+        let plan = index.plan_insert(vec![5.5, 0.0, 0.0, 0.0]).unwrap();
+        let stale = index.plan_insert(vec![6.5, 0.0, 0.0, 0.0]).unwrap();
+        assert_eq!(index.len(), 20);
+
+        let id = index.commit_insert(plan).unwrap();
+        assert_eq!(id, 20);
+        assert!(index.neighbor_count(id, 0) > 0);
+
+        // Second plan was made before the first commit
+        assert!(matches!(index.commit_insert(stale), Err(HNSWError::Internal(_))));
+        assert_eq!(index.len(), 21);
+
+        assert!(matches!(
+            index.plan_insert(vec![1.0; 3]),
+            Err(HNSWError::DimensionMismatch { .. })
+        ));
+    }
+
     #[test]
     fn test_random_level_distribution() {
         let params = HNSWParams::default();
@@ -926,7 +1066,8 @@ mod tests {
 
         // Generate 1000 random levels
         for _ in 0..1000 {
-            let level = index.random_level();
+            let (level, rng_state) = index.next_level();
+            index.rng_state = rng_state;
             level_counts[level as usize] += 1;
         }
 
@@ -1066,7 +1207,7 @@ mod tests {
 
         // Check that no node has more than M*2 neighbors at level 0
         for node in &index.nodes {
-            let neighbor_count = index.neighbors.get_neighbors(node.id, 0).len();
+            let neighbor_count = index.neighbors.neighbors(node.id, 0).len();
             assert!(neighbor_count <= params.m * 2);
         }
     }
@@ -1334,6 +1475,55 @@ mod tests {
         assert!(stats.memory_bytes > 0);
     }
 
+    #[test]
+    fn test_compress_neighbors_preserves_search() {
+        use tempfile::NamedTempFile;
+
+        let params = HNSWParams::default();
+        let mut index = HNSWIndex::new(16, params, DistanceFunction::L2, false).unwrap();
+
+        for vec in random_vectors(3000, 16, 12345) {
+            index.insert(vec).unwrap();
+        }
+        let queries = random_vectors(20, 16, 12346);
+
+        let before: Vec<_> = queries.iter().map(|q| index.search(q, 10, 64).unwrap()).collect();
+        let total_neighbors = index.neighbors.total_neighbors();
+
+        let (bytes_before, bytes_after) = index.compress_neighbors();
+        assert!(index.neighbors_compressed());
+        assert_eq!(index.neighbors.total_neighbors(), total_neighbors);
+        assert!(
+            bytes_after * 2 < bytes_before,
+            "expected at least 2x reduction: {} -> {}",
+            bytes_before,
+            bytes_after
+        );
+
+        for (query, expected) in queries.iter().zip(&before) {
+            let results = index.search(query, 10, 64).unwrap();
+            let ids: Vec<u32> = results.iter().map(|r| r.id).collect();
+            let expected_ids: Vec<u32> = expected.iter().map(|r| r.id).collect();
+            assert_eq!(ids, expected_ids);
+        }
+
+        // Saved files use the uncompressed layout and load normally
+        let temp_file = NamedTempFile::new().unwrap();
+        index.save(temp_file.path()).unwrap();
+        let loaded = HNSWIndex::load(temp_file.path()).unwrap();
+        assert!(!loaded.neighbors_compressed());
+        let results = loaded.search(&queries[0], 10, 64).unwrap();
+        assert_eq!(
+            results.iter().map(|r| r.id).collect::<Vec<_>>(),
+            before[0].iter().map(|r| r.id).collect::<Vec<_>>()
+        );
+
+        // Inserting after compression transparently decompresses
+        index.insert(vec![0.0; 16]).unwrap();
+        assert!(!index.neighbors_compressed());
+        assert_eq!(index.len(), 3001);
+    }
+
     #[test]
     fn test_index_stats_level_distribution() {
         let mut params = HNSWParams::default();
@@ -19,7 +19,7 @@ pub use types::{
 };
 
 // Re-export SIMD-enabled distance functions
-pub use simd_distance::{l2_distance, cosine_distance, dot_product};
+pub use simd_distance::{l2_distance, cosine_distance, dot_product, simd_level, SimdLevel};
 
 pub use storage::{NeighborLists, VectorStorage};
 
@@ -30,6 +30,9 @@ pub struct QueryBuffers {
 
     /// Entry points for layer traversal
     pub entry_points: Vec<u32>,
+
+    /// Decoded neighbor ids (used when neighbor lists are compressed)
+    pub neighbor_ids: Vec<u32>,
 }
 
 impl QueryBuffers {
@@ -44,6 +47,7 @@ impl QueryBuffers {
         self.candidates.clear();
         self.working.clear();
         self.entry_points.clear();
+        self.neighbor_ids.clear();
     }
 
     /// Pre-allocate buffers for expected capacity
@@ -53,6 +57,7 @@ impl QueryBuffers {
             candidates: BinaryHeap::with_capacity(ef),
             working: BinaryHeap::with_capacity(ef),
             entry_points: Vec::with_capacity(num_levels),
+            neighbor_ids: Vec::new(),
         }
     }
 }
@@ -3,6 +3,9 @@
 ///! Uses runtime CPU feature detection to select optimal SIMD implementation.
 ///! Supports AVX-512, AVX2, SSE2 (x86_64) and NEON (ARM).
 ///!
+///! Detection runs once per process (see `simd_level`); every call after that
+///! dispatches on the cached level, so a single binary runs on any CPU.
+///!
 ///! ## Performance
 ///!
 ///! - AVX-512 (16x f32): 3-4x speedup over scalar
@@ -10,70 +13,78 @@
 ///! - SSE2 (4x f32): 1.5-2x speedup over scalar
 ///! - ARM NEON (4x f32): 1.5-2x speedup over scalar
 
+use std::sync::OnceLock;
+
 // Runtime SIMD dispatch
-#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
 
-/// L2 distance (Euclidean) with runtime SIMD detection
+/// SIMD instruction set used by the distance kernels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimdLevel {
+    /// Portable scalar loops
+    Scalar,
+    /// SSE2 (4x f32)
+    Sse2,
+    /// AVX2 + FMA (8x f32)
+    Avx2,
+    /// AVX-512F (16x f32)
+    Avx512,
+    /// ARM NEON (4x f32)
+    Neon,
+}
+
+static SIMD_LEVEL: OnceLock<SimdLevel> = OnceLock::new();
+
+/// SIMD level selected for this process
+///
+/// Detected on first use and cached for the lifetime of the process.
 #[inline]
-pub fn l2_distance(a: &[f32], b: &[f32]) -> f32 {
-    debug_assert_eq!(a.len(), b.len());
+pub fn simd_level() -> SimdLevel {
+    *SIMD_LEVEL.get_or_init(detect_simd_level)
+}
+
+fn detect_simd_level() -> SimdLevel {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx512f") {
+            return SimdLevel::Avx512;
+        }
+    }
 
     #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
     {
-        if is_x86_feature_detected!("avx2") {
-            unsafe { l2_distance_avx2(a, b) }
-        } else if is_x86_feature_detected!("sse2") {
-            unsafe { l2_distance_sse2(a, b) }
-        } else {
-            l2_distance_scalar(a, b)
+        // The AVX2 kernels use fused multiply-add, which is a separate CPUID bit
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            return SimdLevel::Avx2;
+        }
+        if is_x86_feature_detected!("sse2") {
+            return SimdLevel::Sse2;
         }
     }
 
     #[cfg(target_arch = "aarch64")]
     {
         if std::arch::is_aarch64_feature_detected!("neon") {
-            unsafe { l2_distance_neon(a, b) }
-        } else {
-            l2_distance_scalar(a, b)
+            return SimdLevel::Neon;
         }
     }
 
-    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
-    {
-        l2_distance_scalar(a, b)
-    }
+    SimdLevel::Scalar
+}
+
+/// L2 distance (Euclidean) with runtime SIMD detection
+#[inline]
+pub fn l2_distance(a: &[f32], b: &[f32]) -> f32 {
+    l2_distance_at(simd_level(), a, b)
 }
 
 /// Dot product with runtime SIMD detection
 #[inline]
 pub fn dot_product(a: &[f32], b: &[f32]) -> f32 {
-    debug_assert_eq!(a.len(), b.len());
-
-    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-    {
-        if is_x86_feature_detected!("avx2") {
-            unsafe { dot_product_avx2(a, b) }
-        } else if is_x86_feature_detected!("sse2") {
-            unsafe { dot_product_sse2(a, b) }
-        } else {
-            dot_product_scalar(a, b)
-        }
-    }
-
-    #[cfg(target_arch = "aarch64")]
-    {
-        if std::arch::is_aarch64_feature_detected!("neon") {
-            unsafe { dot_product_neon(a, b) }
-        } else {
-            dot_product_scalar(a, b)
-        }
-    }
-
-    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
-    {
-        dot_product_scalar(a, b)
-    }
+    dot_product_at(simd_level(), a, b)
 }
 
 /// Cosine distance with SIMD acceleration
@@ -94,12 +105,108 @@ pub fn cosine_distance(a: &[f32], b: &[f32]) -> f32 {
     1.0 - (dot / (norm_a * norm_b))
 }
 
+/// L2 distance using the kernel for `level`
+///
+/// `level` must be `Scalar` or a level reported by `simd_level()`;
+/// any other value may execute unsupported instructions.
+#[inline]
+fn l2_distance_at(level: SimdLevel, a: &[f32], b: &[f32]) -> f32 {
+    debug_assert_eq!(a.len(), b.len());
+
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { l2_distance_avx512(a, b) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        SimdLevel::Avx2 => unsafe { l2_distance_avx2(a, b) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        SimdLevel::Sse2 => unsafe { l2_distance_sse2(a, b) },
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => unsafe { l2_distance_neon(a, b) },
+        _ => l2_distance_scalar(a, b),
+    }
+}
+
+/// Dot product using the kernel for `level` (same contract as `l2_distance_at`)
+#[inline]
+fn dot_product_at(level: SimdLevel, a: &[f32], b: &[f32]) -> f32 {
+    debug_assert_eq!(a.len(), b.len());
+
+    match level {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx512 => unsafe { dot_product_avx512(a, b) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        SimdLevel::Avx2 => unsafe { dot_product_avx2(a, b) },
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        SimdLevel::Sse2 => unsafe { dot_product_sse2(a, b) },
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => unsafe { dot_product_neon(a, b) },
+        _ => dot_product_scalar(a, b),
+    }
+}
+
+// ============================================================================
+// AVX-512 Implementations (16x f32 lanes)
+// ============================================================================
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+#[inline]
+unsafe fn l2_distance_avx512(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len();
+    let mut sum = _mm512_setzero_ps();
+    let chunks = len / 16;
+
+    // Process 16 floats at a time
+    for i in 0..chunks {
+        let offset = i * 16;
+        let a_vec = _mm512_loadu_ps(a.as_ptr().add(offset));
+        let b_vec = _mm512_loadu_ps(b.as_ptr().add(offset));
+        let diff = _mm512_sub_ps(a_vec, b_vec);
+        sum = _mm512_fmadd_ps(diff, diff, sum); // sum += diff * diff
+    }
+
+    let mut result = _mm512_reduce_add_ps(sum);
+
+    // Handle remainder
+    for i in (chunks * 16)..len {
+        let diff = a[i] - b[i];
+        result += diff * diff;
+    }
+
+    result.sqrt()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+#[inline]
+unsafe fn dot_product_avx512(a: &[f32], b: &[f32]) -> f32 {
+    let len = a.len();
+    let mut sum = _mm512_setzero_ps();
+    let chunks = len / 16;
+
+    for i in 0..chunks {
+        let offset = i * 16;
+        let a_vec = _mm512_loadu_ps(a.as_ptr().add(offset));
+        let b_vec = _mm512_loadu_ps(b.as_ptr().add(offset));
+        sum = _mm512_fmadd_ps(a_vec, b_vec, sum); // sum += a * b
+    }
+
+    let mut result = _mm512_reduce_add_ps(sum);
+
+    // Handle remainder
+    for i in (chunks * 16)..len {
+        result += a[i] * b[i];
+    }
+
+    result
+}
+
 // ============================================================================
 // AVX2 Implementations (8x f32 lanes)
 // ============================================================================
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-#[target_feature(enable = "avx2")]
+#[target_feature(enable = "avx2,fma")]
 #[inline]
 unsafe fn l2_distance_avx2(a: &[f32], b: &[f32]) -> f32 {
     let len = a.len();
@@ -128,7 +235,7 @@ unsafe fn l2_distance_avx2(a: &[f32], b: &[f32]) -> f32 {
 }
 
 #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
-#[target_feature(enable = "avx2")]
+#[target_feature(enable = "avx2,fma")]
 #[inline]
 unsafe fn dot_product_avx2(a: &[f32], b: &[f32]) -> f32 {
     let len = a.len();
@@ -229,7 +336,8 @@ unsafe fn dot_product_sse2(a: &[f32], b: &[f32]) -> f32 {
 #[target_feature(enable = "sse2")]
 #[inline]
 unsafe fn horizontal_sum_sse2(v: __m128) -> f32 {
-    let shuf = _mm_movehdup_ps(v);
+    // Swap adjacent lanes with a plain shuffle (movehdup would need SSE3)
+    let shuf = _mm_shuffle_ps(v, v, 0b10_11_00_01);
     let sums = _mm_add_ps(v, shuf);
     let shuf = _mm_movehl_ps(shuf, sums);
     let sums = _mm_add_ss(sums, shuf);
@@ -357,6 +465,83 @@ mod tests {
         assert!((dist - 0.0).abs() < 1e-6); // Identical vectors
     }
 
+    #[test]
+    fn test_cosine_distance_zero_vector() {
+        let zero = vec![0.0; 16];
+        let b: Vec<f32> = (0..16).map(|i| i as f32).collect();
+
+        // Zero-norm inputs map to maximum distance instead of NaN
+        assert_eq!(cosine_distance(&zero, &b), 1.0);
+        assert_eq!(cosine_distance(&b, &zero), 1.0);
+        assert_eq!(cosine_distance(&zero, &zero), 1.0);
+    }
+
+    #[test]
+    fn test_simd_level_cached() {
+        let level = simd_level();
+        assert_eq!(simd_level(), level);
+        assert_eq!(detect_simd_level(), level);
+    }
+
+    /// Every kernel the running CPU can execute, best first
+    fn supported_levels() -> Vec<SimdLevel> {
+        let mut levels = Vec::new();
+
+        #[cfg(target_arch = "x86_64")]
+        if is_x86_feature_detected!("avx512f") {
+            levels.push(SimdLevel::Avx512);
+        }
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+                levels.push(SimdLevel::Avx2);
+            }
+            if is_x86_feature_detected!("sse2") {
+                levels.push(SimdLevel::Sse2);
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            levels.push(SimdLevel::Neon);
+        }
+
+        levels
+    }
+
+    #[test]
+    fn test_scalar_matches_simd() {
+        // Odd lengths exercise the remainder loops of every kernel width
+        for len in [1, 3, 7, 15, 17, 128, 1000, 1536] {
+            let a: Vec<f32> = (0..len).map(|i| ((i * 37 % 101) as f32 - 50.0) * 0.013).collect();
+            let b: Vec<f32> = (0..len).map(|i| ((i * 53 % 89) as f32 - 44.0) * 0.021).collect();
+
+            let l2_scalar = l2_distance_at(SimdLevel::Scalar, &a, &b);
+            let dot_scalar = dot_product_at(SimdLevel::Scalar, &a, &b);
+
+            for level in supported_levels() {
+                let l2_simd = l2_distance_at(level, &a, &b);
+                assert!(
+                    (l2_scalar - l2_simd).abs() <= 1e-5 * l2_scalar.abs().max(1.0),
+                    "{:?} l2 mismatch at len {}: {} vs {}",
+                    level,
+                    len,
+                    l2_scalar,
+                    l2_simd
+                );
+
+                let dot_simd = dot_product_at(level, &a, &b);
+                assert!(
+                    (dot_scalar - dot_simd).abs() <= 1e-5 * dot_scalar.abs().max(1.0),
+                    "{:?} dot mismatch at len {}: {} vs {}",
+                    level,
+                    len,
+                    dot_scalar,
+                    dot_simd
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_large_vectors() {
         let a: Vec<f32> = (0..1536).map(|i| i as f32).collect();
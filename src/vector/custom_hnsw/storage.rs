@@ -6,19 +6,29 @@
 // - Memory-efficient neighbor list storage
 
 use ordered_float::OrderedFloat;
-use serde::{Deserialize, Serialize};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::borrow::Cow;
 
 /// Storage for neighbor lists
 ///
 /// Neighbors are stored separately from nodes to improve cache utilization.
 /// Only fetch neighbors when traversing the graph.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+///
+/// Lists can optionally be delta-encoded (`compress`) once the graph is built.
+/// Mutations transparently switch back to the uncompressed form.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(from = "RawNeighborLists")]
 pub struct NeighborLists {
     /// Neighbor storage: neighbors[node_id][level] = Vec<neighbor_ids>
     ///
-    /// Simple 2D structure: first index is node_id, second is level
+    /// Simple 2D structure: first index is node_id, second is level.
+    /// Empty while `compressed` is set.
     neighbors: Vec<Vec<Vec<u32>>>,
 
+    /// Delta + varint encoded copy of `neighbors` (see `compress`)
+    compressed: Option<CompressedNeighbors>,
+
     /// Maximum levels supported
     max_levels: usize,
 
@@ -27,11 +37,55 @@ pub struct NeighborLists {
     m_max: usize,
 }
 
+/// On-disk layout of `NeighborLists`
+///
+/// Always the uncompressed lists, so the index file format does not depend on
+/// whether compression was enabled in memory.
+#[derive(Deserialize)]
+struct RawNeighborLists {
+    neighbors: Vec<Vec<Vec<u32>>>,
+    max_levels: usize,
+    m_max: usize,
+}
+
+impl From<RawNeighborLists> for NeighborLists {
+    fn from(raw: RawNeighborLists) -> Self {
+        Self {
+            neighbors: raw.neighbors,
+            compressed: None,
+            max_levels: raw.max_levels,
+            m_max: raw.m_max,
+        }
+    }
+}
+
+impl Serialize for NeighborLists {
+    /// Writes the `RawNeighborLists` layout, borrowing the lists when
+    /// uncompressed and decoding into a temporary only when compressed
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let decoded;
+        let neighbors = match self.compressed {
+            Some(ref compressed) => {
+                decoded = compressed.decode_all(self.max_levels, 0);
+                &decoded
+            }
+            None => &self.neighbors,
+        };
+
+        let mut state = serializer.serialize_struct("NeighborLists", 3)?;
+        state.serialize_field("neighbors", neighbors)?;
+        state.serialize_field("max_levels", &self.max_levels)?;
+        state.serialize_field("m_max", &self.m_max)?;
+        state.end()
+    }
+}
+
 impl NeighborLists {
     /// Create empty neighbor lists
     pub fn new(max_levels: usize) -> Self {
         Self {
             neighbors: Vec::new(),
+            compressed: None,
             max_levels,
             m_max: 32, // Default M*2
         }
@@ -41,6 +95,7 @@ impl NeighborLists {
     pub fn with_capacity(num_nodes: usize, max_levels: usize, m: usize) -> Self {
         Self {
             neighbors: Vec::with_capacity(num_nodes),
+            compressed: None,
             max_levels,
             m_max: m * 2,
         }
@@ -52,7 +107,49 @@ impl NeighborLists {
     }
 
     /// Get neighbors for a node at a specific level
-    pub fn get_neighbors(&self, node_id: u32, level: u8) -> &[u32] {
+    ///
+    /// Borrows when uncompressed; decodes into a new Vec when compressed.
+    /// Hot paths should use `neighbors_buffered` instead.
+    pub fn neighbors(&self, node_id: u32, level: u8) -> Cow<'_, [u32]> {
+        if let Some(ref compressed) = self.compressed {
+            let mut out = Vec::new();
+            compressed.decode_into(node_id as usize, level as usize, &mut out);
+            return Cow::Owned(out);
+        }
+
+        Cow::Borrowed(self.raw_neighbors(node_id, level))
+    }
+
+    /// Get neighbors, decoding into `scratch` only when compressed
+    ///
+    /// Allocation-free once `scratch` has grown to M_max.
+    #[inline]
+    pub fn neighbors_buffered<'a>(
+        &'a self,
+        node_id: u32,
+        level: u8,
+        scratch: &'a mut Vec<u32>,
+    ) -> &'a [u32] {
+        match self.compressed {
+            Some(ref compressed) => {
+                scratch.clear();
+                compressed.decode_into(node_id as usize, level as usize, scratch);
+                scratch
+            }
+            None => self.raw_neighbors(node_id, level),
+        }
+    }
+
+    /// Number of neighbors for a node at a level (no decoding or allocation)
+    pub fn neighbor_count(&self, node_id: u32, level: u8) -> usize {
+        match self.compressed {
+            Some(ref compressed) => compressed.count(node_id as usize, level as usize),
+            None => self.raw_neighbors(node_id, level).len(),
+        }
+    }
+
+    #[inline]
+    fn raw_neighbors(&self, node_id: u32, level: u8) -> &[u32] {
         let node_idx = node_id as usize;
         let level_idx = level as usize;
 
@@ -67,8 +164,35 @@ impl NeighborLists {
         &self.neighbors[node_idx][level_idx]
     }
 
+    /// Delta-encode all neighbor lists
+    ///
+    /// Each list is sorted and stored as varint-encoded gaps, typically
+    /// 1-2 bytes per neighbor instead of 4. Intended for read-mostly indexes;
+    /// any later mutation decompresses first.
+    pub fn compress(&mut self) {
+        if self.compressed.is_some() {
+            return;
+        }
+        self.compressed = Some(CompressedNeighbors::encode(&self.neighbors));
+        self.neighbors = Vec::new();
+    }
+
+    /// Restore the uncompressed representation (no-op if not compressed)
+    pub fn decompress(&mut self) {
+        if let Some(compressed) = self.compressed.take() {
+            self.neighbors = compressed.decode_all(self.max_levels, self.m_max);
+        }
+    }
+
+    /// Whether neighbor lists are currently delta-encoded
+    pub fn is_compressed(&self) -> bool {
+        self.compressed.is_some()
+    }
+
     /// Set neighbors for a node at a specific level
     pub fn set_neighbors(&mut self, node_id: u32, level: u8, neighbors_list: Vec<u32>) {
+        self.decompress();
+
         let node_idx = node_id as usize;
         let level_idx = level as usize;
 
@@ -87,6 +211,8 @@ impl NeighborLists {
 
     /// Add a bidirectional link between two nodes at a level
     pub fn add_bidirectional_link(&mut self, node_a: u32, node_b: u32, level: u8) {
+        self.decompress();
+
         let node_a_idx = node_a as usize;
         let node_b_idx = node_b as usize;
         let level_idx = level as usize;
@@ -114,6 +240,10 @@ impl NeighborLists {
 
     /// Get total number of neighbor entries
     pub fn total_neighbors(&self) -> usize {
+        if let Some(ref compressed) = self.compressed {
+            return compressed.total_neighbors();
+        }
+
         self.neighbors
             .iter()
             .flat_map(|node| node.iter())
@@ -123,6 +253,10 @@ impl NeighborLists {
 
     /// Get memory usage in bytes (approximate)
     pub fn memory_usage(&self) -> usize {
+        if let Some(ref compressed) = self.compressed {
+            return compressed.memory_usage();
+        }
+
         let mut total = 0;
 
         // Size of outer Vec
@@ -150,6 +284,9 @@ impl NeighborLists {
     pub fn reorder_bfs(&mut self, entry_point: u32, start_level: u8) -> Vec<u32> {
         use std::collections::{HashSet, VecDeque};
 
+        let was_compressed = self.is_compressed();
+        self.decompress();
+
         let num_nodes = self.neighbors.len();
         if num_nodes == 0 {
             return Vec::new();
@@ -172,7 +309,7 @@ impl NeighborLists {
 
             // Visit neighbors at all levels (starting from highest)
             for level in (0..=start_level).rev() {
-                let neighbors = self.get_neighbors(node_id, level);
+                let neighbors = self.raw_neighbors(node_id, level);
                 for &neighbor_id in neighbors {
                     if visited.insert(neighbor_id) {
                         queue.push_back(neighbor_id);
@@ -205,12 +342,175 @@ impl NeighborLists {
 
         self.neighbors = new_neighbors;
 
+        if was_compressed {
+            self.compress();
+        }
+
         old_to_new
     }
 
     /// Get number of nodes
     pub fn num_nodes(&self) -> usize {
-        self.neighbors.len()
+        match self.compressed {
+            Some(ref compressed) => compressed.num_nodes(),
+            None => self.neighbors.len(),
+        }
+    }
+}
+
+/// Delta + varint encoded neighbor lists
+///
+/// Per node: for each level (trailing empty levels omitted), a varint count
+/// followed by the sorted ids as varint gaps (first id absolute). Level 0 comes
+/// first so the common lookup never skips data.
+#[derive(Clone, Debug)]
+struct CompressedNeighbors {
+    /// Encoded bytes for all nodes
+    data: Vec<u8>,
+
+    /// Byte range of node i is offsets[i]..offsets[i + 1]
+    offsets: Vec<u64>,
+}
+
+impl CompressedNeighbors {
+    fn encode(neighbors: &[Vec<Vec<u32>>]) -> Self {
+        let mut data = Vec::new();
+        let mut offsets = Vec::with_capacity(neighbors.len() + 1);
+        offsets.push(0);
+
+        let mut sorted = Vec::new();
+        for levels in neighbors {
+            let used_levels = levels.iter().rposition(|l| !l.is_empty()).map_or(0, |i| i + 1);
+            for list in &levels[..used_levels] {
+                sorted.clear();
+                sorted.extend_from_slice(list);
+                sorted.sort_unstable();
+
+                write_varint(&mut data, sorted.len() as u32);
+                let mut prev = 0u32;
+                for &id in &sorted {
+                    write_varint(&mut data, id - prev);
+                    prev = id;
+                }
+            }
+            offsets.push(data.len() as u64);
+        }
+
+        data.shrink_to_fit();
+        Self { data, offsets }
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Encoded bytes of (node, level), starting at its count
+    ///
+    /// Empty when the node or level has no neighbors.
+    #[inline]
+    fn level_bytes(&self, node_idx: usize, level_idx: usize) -> &[u8] {
+        if node_idx >= self.num_nodes() {
+            return &[];
+        }
+
+        let mut bytes = &self.data[self.offsets[node_idx] as usize..self.offsets[node_idx + 1] as usize];
+
+        for _ in 0..level_idx {
+            if bytes.is_empty() {
+                return &[];
+            }
+            let count = read_varint(&mut bytes);
+            for _ in 0..count {
+                read_varint(&mut bytes);
+            }
+        }
+
+        bytes
+    }
+
+    /// Number of neighbors of (node, level)
+    fn count(&self, node_idx: usize, level_idx: usize) -> usize {
+        let mut bytes = self.level_bytes(node_idx, level_idx);
+        if bytes.is_empty() {
+            return 0;
+        }
+        read_varint(&mut bytes) as usize
+    }
+
+    /// Append the neighbors of (node, level) to `out`
+    #[inline]
+    fn decode_into(&self, node_idx: usize, level_idx: usize, out: &mut Vec<u32>) {
+        let mut bytes = self.level_bytes(node_idx, level_idx);
+        if bytes.is_empty() {
+            return;
+        }
+
+        let count = read_varint(&mut bytes);
+        out.reserve(count as usize);
+        let mut prev = 0u32;
+        for _ in 0..count {
+            prev += read_varint(&mut bytes);
+            out.push(prev);
+        }
+    }
+
+    fn decode_all(&self, max_levels: usize, m_max: usize) -> Vec<Vec<Vec<u32>>> {
+        (0..self.num_nodes())
+            .map(|node_idx| {
+                (0..max_levels)
+                    .map(|level_idx| {
+                        let mut list = Vec::with_capacity(m_max);
+                        self.decode_into(node_idx, level_idx, &mut list);
+                        list
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn total_neighbors(&self) -> usize {
+        let mut total = 0;
+        for node_idx in 0..self.num_nodes() {
+            let mut bytes = &self.data[self.offsets[node_idx] as usize..self.offsets[node_idx + 1] as usize];
+            while !bytes.is_empty() {
+                let count = read_varint(&mut bytes);
+                for _ in 0..count {
+                    read_varint(&mut bytes);
+                }
+                total += count as usize;
+            }
+        }
+        total
+    }
+
+    fn memory_usage(&self) -> usize {
+        self.data.capacity() + self.offsets.capacity() * std::mem::size_of::<u64>()
+    }
+}
+
+/// LEB128 encode a u32 (1-5 bytes)
+#[inline]
+fn write_varint(out: &mut Vec<u8>, mut value: u32) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// LEB128 decode a u32, advancing `bytes`
+#[inline]
+fn read_varint(bytes: &mut &[u8]) -> u32 {
+    let mut value = 0u32;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[0];
+        *bytes = &bytes[1..];
+        value |= ((byte & 0x7F) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
     }
 }
 
@@ -486,11 +786,11 @@ mod tests {
         // Set neighbors for node 0, level 0
         lists.set_neighbors(0, 0, vec![1, 2, 3]);
 
-        let neighbors = lists.get_neighbors(0, 0);
-        assert_eq!(neighbors, &[1, 2, 3]);
+        let neighbors = lists.neighbors(0, 0);
+        assert_eq!(&*neighbors, &[1, 2, 3]);
 
         // Empty level
-        let empty = lists.get_neighbors(0, 1);
+        let empty = lists.neighbors(0, 1);
         assert_eq!(empty.len(), 0);
     }
 
@@ -500,8 +800,58 @@ mod tests {
 
         lists.add_bidirectional_link(0, 1, 0);
 
-        assert_eq!(lists.get_neighbors(0, 0), &[1]);
-        assert_eq!(lists.get_neighbors(1, 0), &[0]);
+        assert_eq!(&*lists.neighbors(0, 0), &[1]);
+        assert_eq!(&*lists.neighbors(1, 0), &[0]);
+    }
+
+    #[test]
+    fn test_neighbor_lists_compress_roundtrip() {
+        let mut lists = NeighborLists::new(4);
+
+        // Unsorted ids and multi-byte varints (ids >= 128, >= 16384)
+        lists.set_neighbors(0, 0, vec![300, 2, 70_000, 1]);
+        lists.set_neighbors(0, 2, vec![5]);
+        lists.set_neighbors(3, 0, vec![u32::MAX, 0]);
+        let uncompressed = lists.clone();
+
+        lists.compress();
+        assert!(lists.is_compressed());
+        assert_eq!(lists.num_nodes(), 4);
+        assert_eq!(lists.total_neighbors(), uncompressed.total_neighbors());
+
+        // Lists come back sorted; levels without neighbors stay empty
+        assert_eq!(&*lists.neighbors(0, 0), &[1, 2, 300, 70_000]);
+        assert!(lists.neighbors(0, 1).is_empty());
+        assert_eq!(&*lists.neighbors(0, 2), &[5]);
+        assert!(lists.neighbors(0, 3).is_empty());
+        assert!(lists.neighbors(1, 0).is_empty());
+        assert_eq!(&*lists.neighbors(3, 0), &[0, u32::MAX]);
+        assert!(lists.neighbors(99, 0).is_empty());
+
+        let mut scratch = Vec::new();
+        assert_eq!(lists.neighbors_buffered(0, 2, &mut scratch), &[5]);
+
+        // Serializes in the uncompressed layout
+        let encoded = bincode::serialize(&lists).unwrap();
+        let decoded: NeighborLists = bincode::deserialize(&encoded).unwrap();
+        assert!(!decoded.is_compressed());
+        assert_eq!(decoded.num_nodes(), uncompressed.num_nodes());
+        assert_eq!(&*decoded.neighbors(0, 0), &[1, 2, 300, 70_000]);
+        assert_eq!(&*decoded.neighbors(0, 2), &[5]);
+        assert_eq!(&*decoded.neighbors(3, 0), &[0, u32::MAX]);
+        assert_eq!(bincode::serialize(&decoded).unwrap(), encoded);
+
+        // Counts come straight from the encoded form
+        assert_eq!(lists.neighbor_count(0, 0), 4);
+        assert_eq!(lists.neighbor_count(0, 1), 0);
+        assert_eq!(lists.neighbor_count(0, 2), 1);
+        assert_eq!(lists.neighbor_count(99, 0), 0);
+
+        // Mutation decompresses and keeps existing lists
+        lists.add_bidirectional_link(1, 2, 0);
+        assert!(!lists.is_compressed());
+        assert_eq!(&*lists.neighbors(0, 0), &[1, 2, 300, 70_000]);
+        assert_eq!(&*lists.neighbors(1, 0), &[2]);
     }
 
     #[test]
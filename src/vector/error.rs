@@ -0,0 +1,53 @@
+//! Error types for VectorStore operations
+
+use thiserror::Error;
+
+/// Errors returned by `VectorStore` for invalid input
+///
+/// `VectorStore` methods return `anyhow::Result`; callers that need to
+/// distinguish failure modes can `downcast_ref::<VectorError>()`.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum VectorError {
+    /// Vector length doesn't match the store dimensions
+    #[error("Vector dimension mismatch: expected {expected}, got {actual}")]
+    DimensionMismatch {
+        expected: usize,
+        actual: usize,
+    },
+
+    /// Vector contains NaN or infinite components
+    #[error("Vector contains invalid values (NaN or Infinity) at index {index}")]
+    NonFinite {
+        index: usize,
+    },
+
+    /// Per-query ef_search smaller than the requested k
+    #[error("Invalid ef_search: {ef_search} (must be >= k={k})")]
+    InvalidEfSearch {
+        k: usize,
+        ef_search: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_messages() {
+        let err = VectorError::DimensionMismatch { expected: 128, actual: 64 };
+        assert_eq!(err.to_string(), "Vector dimension mismatch: expected 128, got 64");
+
+        let err = VectorError::NonFinite { index: 3 };
+        assert!(err.to_string().contains("NaN or Infinity"));
+    }
+
+    #[test]
+    fn test_downcast_through_anyhow() {
+        let err: anyhow::Error = VectorError::DimensionMismatch { expected: 3, actual: 4 }.into();
+        assert_eq!(
+            err.downcast_ref::<VectorError>(),
+            Some(&VectorError::DimensionMismatch { expected: 3, actual: 4 })
+        );
+    }
+}
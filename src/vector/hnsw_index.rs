@@ -15,7 +15,9 @@ use super::custom_hnsw::{
     HNSWParams as CoreParams,
 };
 use anyhow::Result;
+use std::ops::{Deref, DerefMut};
 use std::path::Path;
+use std::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 /// HNSW index for approximate nearest neighbor search
 #[derive(Debug)]
@@ -159,6 +161,14 @@ impl HNSWIndex {
     /// # Returns
     /// Vector of (ID, distance) tuples, sorted by distance (ascending)
     pub fn search(&self, query: &[f32], k: usize) -> Result<Vec<(usize, f32)>> {
+        self.search_with_ef(query, k, self.ef_search)
+    }
+
+    /// Search for K nearest neighbors with an explicit ef_search
+    ///
+    /// Overrides the index-wide ef_search for this query only.
+    /// `ef_search` must be >= `k`.
+    pub fn search_with_ef(&self, query: &[f32], k: usize, ef_search: usize) -> Result<Vec<(usize, f32)>> {
         if query.len() != self.dimensions {
             anyhow::bail!(
                 "Query dimension mismatch: expected {}, got {}",
@@ -168,7 +178,7 @@ impl HNSWIndex {
         }
 
         // Search with HNSW
-        let results = self.index.search(query, k, self.ef_search).map_err(|e| anyhow::anyhow!(e))?;
+        let results = self.index.search(query, k, ef_search).map_err(|e| anyhow::anyhow!(e))?;
 
         // Convert to (id, distance) tuples
         let neighbors: Vec<(usize, f32)> = results
@@ -273,9 +283,147 @@ impl HNSWIndex {
     }
 }
 
+/// HNSW index shared between concurrent searchers and inserters
+///
+/// Searches take a shared read lock and run in parallel. Inserts are split
+/// in two phases: neighbor selection runs under the read lock (alongside
+/// searches), then linking runs under a short write lock. A search therefore
+/// sees a new node either fully linked or not at all.
+///
+/// Inserters, and holders of `write()`, are serialized among themselves so a
+/// plan stays valid until it is committed.
+///
+/// # Example
+/// ```ignore
+/// use omen::vector::ConcurrentHNSWIndex;
+/// use std::sync::Arc;
+///
+/// let index = Arc::new(ConcurrentHNSWIndex::new(HNSWIndex::new(1_000_000, 1536)));
+/// index.insert(&vector)?;             // from any thread
+/// let results = index.search(&query, 10)?;
+/// ```
+#[derive(Debug)]
+pub struct ConcurrentHNSWIndex {
+    index: RwLock<HNSWIndex>,
+
+    /// Held for the whole insert (plan + commit)
+    insert_lock: Mutex<()>,
+}
+
+impl ConcurrentHNSWIndex {
+    /// Wrap an existing index
+    pub fn new(index: HNSWIndex) -> Self {
+        Self {
+            index: RwLock::new(index),
+            insert_lock: Mutex::new(()),
+        }
+    }
+
+    /// Insert vector into index and return its ID
+    ///
+    /// The write lock is only held while linking the new node.
+    pub fn insert(&self, vector: &[f32]) -> Result<usize> {
+        let _insert_guard = self
+            .insert_lock
+            .lock()
+            .map_err(|_| anyhow::anyhow!("HNSW insert lock poisoned"))?;
+
+        let plan = self
+            .read()?
+            .index
+            .plan_insert(vector.to_vec())
+            .map_err(|e| anyhow::anyhow!(e))?;
+
+        let mut index = self.write_index()?;
+        let id = index.index.commit_insert(plan).map_err(|e| anyhow::anyhow!(e))?;
+        index.num_vectors += 1;
+        Ok(id as usize)
+    }
+
+    /// Search for K nearest neighbors (see `HNSWIndex::search`)
+    pub fn search(&self, query: &[f32], k: usize) -> Result<Vec<(usize, f32)>> {
+        self.read()?.search(query, k)
+    }
+
+    /// Search with an explicit ef_search (see `HNSWIndex::search_with_ef`)
+    pub fn search_with_ef(&self, query: &[f32], k: usize, ef_search: usize) -> Result<Vec<(usize, f32)>> {
+        self.read()?.search_with_ef(query, k, ef_search)
+    }
+
+    /// Number of vectors in index
+    pub fn len(&self) -> Result<usize> {
+        Ok(self.read()?.len())
+    }
+
+    /// Check if index is empty
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.read()?.is_empty())
+    }
+
+    /// Shared access to the underlying index
+    pub fn read(&self) -> Result<RwLockReadGuard<'_, HNSWIndex>> {
+        self.index
+            .read()
+            .map_err(|_| anyhow::anyhow!("HNSW index lock poisoned"))
+    }
+
+    /// Exclusive access to the underlying index (blocks all searches)
+    ///
+    /// Also waits for any in-flight `insert`, so mutations through the guard
+    /// never invalidate an insert plan.
+    pub fn write(&self) -> Result<ConcurrentWriteGuard<'_>> {
+        let insert_guard = self
+            .insert_lock
+            .lock()
+            .map_err(|_| anyhow::anyhow!("HNSW insert lock poisoned"))?;
+
+        Ok(ConcurrentWriteGuard {
+            index: self.write_index()?,
+            _insert_guard: insert_guard,
+        })
+    }
+
+    fn write_index(&self) -> Result<RwLockWriteGuard<'_, HNSWIndex>> {
+        self.index
+            .write()
+            .map_err(|_| anyhow::anyhow!("HNSW index lock poisoned"))
+    }
+
+    /// Unwrap into the underlying index
+    pub fn into_inner(self) -> Result<HNSWIndex> {
+        self.index
+            .into_inner()
+            .map_err(|_| anyhow::anyhow!("HNSW index lock poisoned"))
+    }
+}
+
+/// Exclusive access to a `ConcurrentHNSWIndex` (see `write`)
+///
+/// Holds both the index write lock and the insert lock.
+pub struct ConcurrentWriteGuard<'a> {
+    // Declared first so the index lock is released before the insert lock
+    index: RwLockWriteGuard<'a, HNSWIndex>,
+    _insert_guard: MutexGuard<'a, ()>,
+}
+
+impl Deref for ConcurrentWriteGuard<'_> {
+    type Target = HNSWIndex;
+
+    fn deref(&self) -> &HNSWIndex {
+        &self.index
+    }
+}
+
+impl DerefMut for ConcurrentWriteGuard<'_> {
+    fn deref_mut(&mut self) -> &mut HNSWIndex {
+        &mut self.index
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::vector::custom_hnsw::HNSWError;
 
     #[test]
     fn test_hnsw_basic() {
@@ -325,4 +473,34 @@ mod tests {
         index.set_ef_search(200);
         assert_eq!(index.get_ef_search(), 200);
     }
+
+    #[test]
+    fn test_concurrent_index_insert_matches_sequential() {
+        let vectors: Vec<Vec<f32>> = (0..200)
+            .map(|i| (0..8).map(|d| ((i * 31 + d * 7) % 97) as f32 / 97.0).collect())
+            .collect();
+
+        let mut sequential = HNSWIndex::new(1000, 8);
+        let concurrent = ConcurrentHNSWIndex::new(HNSWIndex::new(1000, 8));
+        for v in &vectors {
+            assert_eq!(sequential.insert(v).unwrap(), concurrent.insert(v).unwrap());
+        }
+        assert_eq!(concurrent.len().unwrap(), 200);
+
+        // Same plan/commit steps as insert(), so the graphs are identical
+        for v in vectors.iter().step_by(10) {
+            assert_eq!(sequential.search(v, 5).unwrap(), concurrent.search(v, 5).unwrap());
+        }
+
+        let err = concurrent.insert(&[1.0; 4]).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<HNSWError>(),
+            Some(HNSWError::DimensionMismatch { expected: 8, actual: 4 })
+        ));
+
+        // Mutations through the write guard don't break later inserts
+        concurrent.write().unwrap().insert(&vectors[0]).unwrap();
+        assert_eq!(concurrent.insert(&vectors[1]).unwrap(), 201);
+        assert_eq!(concurrent.into_inner().unwrap().len(), 202);
+    }
 }
@@ -9,15 +9,20 @@
 //! - Memory: <200 bytes/vector overhead
 
 pub mod types;
+pub mod error;
 pub mod store;
 pub mod hnsw_index;
 pub mod vector_value;
 pub mod custom_hnsw; // Internal implementation
 pub mod extended_rabitq; // Extended RaBitQ quantization (SIGMOD 2025)
 
+#[cfg(test)]
+pub(crate) mod test_utils;
+
 // Re-export main types
 pub use types::Vector;
+pub use error::VectorError;
 pub use store::VectorStore;
-pub use hnsw_index::HNSWIndex;
+pub use hnsw_index::{ConcurrentHNSWIndex, ConcurrentWriteGuard, HNSWIndex};
 pub use vector_value::VectorValue;
 pub use extended_rabitq::{ExtendedRaBitQ, ExtendedRaBitQParams, QuantizationBits, QuantizedVector};
@@ -7,6 +7,7 @@
 
 use super::hnsw_index::HNSWIndex;
 use super::types::Vector;
+use super::error::VectorError;
 use super::extended_rabitq::{ExtendedRaBitQ, ExtendedRaBitQParams, QuantizedVector};
 use anyhow::Result;
 
@@ -56,13 +57,8 @@ impl VectorStore {
 
     /// Insert vector and return its ID
     pub fn insert(&mut self, vector: Vector) -> Result<usize> {
-        if vector.dim() != self.dimensions {
-            anyhow::bail!(
-                "Vector dimension mismatch: expected {}, got {}",
-                self.dimensions,
-                vector.dim()
-            );
-        }
+        self.check_dimensions(&vector)?;
+        Self::check_finite(&vector)?;
 
         let id = self.vectors.len();
 
@@ -106,15 +102,9 @@ impl VectorStore {
         }
 
         // Validate dimensions
-        for (i, vector) in vectors.iter().enumerate() {
-            if vector.dim() != self.dimensions {
-                anyhow::bail!(
-                    "Vector {} dimension mismatch: expected {}, got {}",
-                    i,
-                    self.dimensions,
-                    vector.dim()
-                );
-            }
+        for vector in &vectors {
+            self.check_dimensions(vector)?;
+            Self::check_finite(vector)?;
         }
 
         // Lazy initialize HNSW on first insert
@@ -218,13 +208,8 @@ impl VectorStore {
     /// Quantization (if enabled) is for storage/memory savings only.
     /// Search always uses HNSW with original vectors for accuracy and speed.
     pub fn knn_search(&mut self, query: &Vector, k: usize) -> Result<Vec<(usize, f32)>> {
-        if query.dim() != self.dimensions {
-            anyhow::bail!(
-                "Query dimension mismatch: expected {}, got {}",
-                self.dimensions,
-                query.dim()
-            );
-        }
+        self.check_dimensions(query)?;
+        Self::check_finite(query)?;
 
         // Check if we have any data (either in vectors or in HNSW)
         let has_data = !self.vectors.is_empty() ||
@@ -252,6 +237,30 @@ impl VectorStore {
         self.knn_search_brute_force(query, k)
     }
 
+    /// K-nearest neighbors search with a per-query ef_search
+    ///
+    /// Small `ef_search` favors latency, large favors recall; the store-wide
+    /// setting (`set_ef_search`) is left unchanged. Requires `ef_search >= k`.
+    pub fn knn_search_with_ef(
+        &self,
+        query: &Vector,
+        k: usize,
+        ef_search: usize,
+    ) -> Result<Vec<(usize, f32)>> {
+        self.check_dimensions(query)?;
+        Self::check_finite(query)?;
+
+        if ef_search < k {
+            return Err(VectorError::InvalidEfSearch { k, ef_search }.into());
+        }
+
+        match self.hnsw_index {
+            Some(ref index) if k > 0 => index.search_with_ef(&query.data, k, ef_search),
+            Some(_) => Ok(Vec::new()),
+            None => self.knn_search_brute_force(query, k),
+        }
+    }
+
     /// Two-phase search with quantization + reranking
     ///
     /// Phase 1: Use quantized vectors for fast filtering (get k*3 candidates)
@@ -297,13 +306,8 @@ impl VectorStore {
 
     /// Brute-force K-NN search (fallback, mainly for testing)
     pub fn knn_search_brute_force(&self, query: &Vector, k: usize) -> Result<Vec<(usize, f32)>> {
-        if query.dim() != self.dimensions {
-            anyhow::bail!(
-                "Query dimension mismatch: expected {}, got {}",
-                self.dimensions,
-                query.dim()
-            );
-        }
+        self.check_dimensions(query)?;
+        Self::check_finite(query)?;
 
         if self.vectors.is_empty() {
             return Ok(Vec::new());
@@ -325,6 +329,96 @@ impl VectorStore {
         Ok(distances.into_iter().take(k).collect())
     }
 
+    /// Exact K-NN ids for use as recall ground truth
+    ///
+    /// Same ordering as `knn_search_brute_force`, in the id format expected
+    /// by `measure_recall`.
+    pub fn brute_force_knn(&self, query: &Vector, k: usize) -> Result<Vec<u32>> {
+        Ok(self
+            .knn_search_brute_force(query, k)?
+            .into_iter()
+            .map(|(id, _)| id as u32)
+            .collect())
+    }
+
+    /// Mean recall@k of the HNSW index against provided ground truth
+    ///
+    /// For each query, recall is the fraction of the top-k ground truth ids
+    /// present in the top-k HNSW results. Uses the current `ef_search`.
+    ///
+    /// Errors if no HNSW index has been built or it is empty (e.g. a store
+    /// loaded without `rebuild_index`), rather than scoring brute force
+    /// against itself.
+    pub fn measure_recall(
+        &self,
+        queries: &[Vector],
+        ground_truth: &[Vec<u32>],
+        k: usize,
+    ) -> Result<f64> {
+        if queries.len() != ground_truth.len() {
+            anyhow::bail!(
+                "Ground truth count mismatch: {} queries, {} ground truth lists",
+                queries.len(),
+                ground_truth.len()
+            );
+        }
+        if queries.is_empty() || k == 0 {
+            anyhow::bail!("Recall requires at least one query and k > 0");
+        }
+
+        let index = match self.hnsw_index {
+            Some(ref index) if !index.is_empty() => index,
+            _ => anyhow::bail!("Cannot measure recall: no HNSW index has been built"),
+        };
+
+        let mut total_recall = 0.0;
+
+        for (query, truth) in queries.iter().zip(ground_truth) {
+            self.check_dimensions(query)?;
+            Self::check_finite(query)?;
+
+            let expected: std::collections::HashSet<u32> = truth.iter().take(k).copied().collect();
+            if expected.is_empty() {
+                // Nothing to find: any result set is trivially complete
+                total_recall += 1.0;
+                continue;
+            }
+
+            let results = index.search(&query.data, k)?;
+
+            let hits = results
+                .iter()
+                .filter(|(id, _)| expected.contains(&(*id as u32)))
+                .count();
+            total_recall += hits as f64 / expected.len() as f64;
+        }
+
+        Ok(total_recall / queries.len() as f64)
+    }
+
+    /// Reject vectors whose length differs from the store dimensions
+    fn check_dimensions(&self, vector: &Vector) -> Result<()> {
+        if vector.dim() != self.dimensions {
+            return Err(VectorError::DimensionMismatch {
+                expected: self.dimensions,
+                actual: vector.dim(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Reject vectors containing NaN or infinite components
+    ///
+    /// Non-finite values poison distance comparisons in the HNSW candidate heap
+    /// and the brute-force sort, so both inserts and queries are checked.
+    fn check_finite(vector: &Vector) -> Result<()> {
+        if let Some(index) = vector.data.iter().position(|x| !x.is_finite()) {
+            return Err(VectorError::NonFinite { index }.into());
+        }
+        Ok(())
+    }
+
     /// Get vector by ID
     pub fn get(&self, id: usize) -> Option<&Vector> {
         self.vectors.get(id)
@@ -568,6 +662,7 @@ impl VectorStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::vector::test_utils::random_vectors;
 
     fn random_vector(dim: usize, seed: usize) -> Vector {
         let data: Vec<f32> = (0..dim).map(|i| ((seed + i) as f32) * 0.1).collect();
@@ -636,7 +731,154 @@ mod tests {
         let mut store = VectorStore::new(128);
         let wrong_dim = Vector::new(vec![1.0; 64]);
 
-        assert!(store.insert(wrong_dim).is_err());
+        let err = store.insert(wrong_dim.clone()).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<VectorError>(),
+            Some(&VectorError::DimensionMismatch { expected: 128, actual: 64 })
+        );
+
+        let err = store
+            .batch_insert(vec![random_vector(128, 0), wrong_dim])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<VectorError>(),
+            Some(&VectorError::DimensionMismatch { expected: 128, actual: 64 })
+        );
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_search_dimension_mismatch() {
+        let mut store = VectorStore::new(128);
+        for i in 0..10 {
+            store.insert(random_vector(128, i)).unwrap();
+        }
+
+        let wrong_query = Vector::new(vec![1.0; 256]);
+        let expected = VectorError::DimensionMismatch { expected: 128, actual: 256 };
+
+        let err = store.knn_search(&wrong_query, 5).unwrap_err();
+        assert_eq!(err.downcast_ref::<VectorError>(), Some(&expected));
+
+        let err = store.knn_search_brute_force(&wrong_query, 5).unwrap_err();
+        assert_eq!(err.downcast_ref::<VectorError>(), Some(&expected));
+    }
+
+    #[test]
+    fn test_non_finite_rejected() {
+        let mut store = VectorStore::new(4);
+
+        let err = store.insert(Vector::new(vec![1.0, f32::NAN, 0.0, 0.0])).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<VectorError>(),
+            Some(&VectorError::NonFinite { index: 1 })
+        );
+
+        let err = store
+            .batch_insert(vec![
+                Vector::new(vec![1.0; 4]),
+                Vector::new(vec![0.0, 0.0, 0.0, f32::INFINITY]),
+            ])
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<VectorError>(),
+            Some(&VectorError::NonFinite { index: 3 })
+        );
+
+        // Nothing was stored from the rejected inserts
+        assert!(store.is_empty());
+        assert!(store.hnsw_index.is_none());
+
+        // Queries are rejected too, with and without an index
+        let nan_query = Vector::new(vec![0.0, 0.0, f32::NAN, 0.0]);
+        let expected = VectorError::NonFinite { index: 2 };
+        store.insert(Vector::new(vec![1.0; 4])).unwrap();
+
+        let err = store.knn_search_brute_force(&nan_query, 1).unwrap_err();
+        assert_eq!(err.downcast_ref::<VectorError>(), Some(&expected));
+        let err = store.brute_force_knn(&nan_query, 1).unwrap_err();
+        assert_eq!(err.downcast_ref::<VectorError>(), Some(&expected));
+        let err = store.knn_search(&nan_query, 1).unwrap_err();
+        assert_eq!(err.downcast_ref::<VectorError>(), Some(&expected));
+
+        store.hnsw_index = None;
+        let err = store.knn_search_with_ef(&nan_query, 1, 10).unwrap_err();
+        assert_eq!(err.downcast_ref::<VectorError>(), Some(&expected));
+    }
+
+    #[test]
+    fn test_measure_recall_exact() {
+        let mut store = VectorStore::new(16);
+        for data in random_vectors(200, 16, 42) {
+            store.insert(Vector::new(data)).unwrap();
+        }
+
+        let queries: Vec<Vector> = random_vectors(20, 16, 43).into_iter().map(Vector::new).collect();
+        let ground_truth: Vec<Vec<u32>> = queries
+            .iter()
+            .map(|q| store.brute_force_knn(q, 10).unwrap())
+            .collect();
+
+        // ef well above the dataset size makes the search exhaustive
+        store.set_ef_search(400);
+        let recall = store.measure_recall(&queries, &ground_truth, 10).unwrap();
+        assert_eq!(recall, 1.0);
+
+        // Mismatched query / ground truth counts are rejected
+        assert!(store.measure_recall(&queries[..5], &ground_truth, 10).is_err());
+
+        // No index (e.g. loaded without rebuild_index) is an error, not recall 1.0
+        store.hnsw_index = None;
+        assert!(store.measure_recall(&queries, &ground_truth, 10).is_err());
+        assert!(VectorStore::new(16).measure_recall(&queries, &ground_truth, 10).is_err());
+    }
+
+    #[test]
+    fn test_knn_search_with_ef_recall() {
+        let mut store = VectorStore::new(32);
+        for data in random_vectors(2000, 32, 7) {
+            store.insert(Vector::new(data)).unwrap();
+        }
+
+        let query = Vector::new(random_vectors(1, 32, 8).remove(0));
+        let k = 20;
+        let truth: std::collections::HashSet<u32> =
+            store.brute_force_knn(&query, k).unwrap().into_iter().collect();
+
+        let mut previous = 0.0;
+        for ef in [20, 40, 80, 160, 2000] {
+            let results = store.knn_search_with_ef(&query, k, ef).unwrap();
+            assert_eq!(results.len(), k);
+
+            let hits = results.iter().filter(|(id, _)| truth.contains(&(*id as u32))).count();
+            let recall = hits as f64 / k as f64;
+            assert!(
+                recall >= previous,
+                "recall dropped from {} to {} at ef={}",
+                previous,
+                recall,
+                ef
+            );
+            previous = recall;
+        }
+        assert_eq!(previous, 1.0);
+
+        // Store-wide default is untouched
+        assert_eq!(store.get_ef_search(), Some(100));
+    }
+
+    #[test]
+    fn test_knn_search_with_ef_below_k() {
+        let mut store = VectorStore::new(128);
+        for i in 0..10 {
+            store.insert(random_vector(128, i)).unwrap();
+        }
+
+        let err = store.knn_search_with_ef(&random_vector(128, 0), 10, 5).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<VectorError>(),
+            Some(&VectorError::InvalidEfSearch { k: 10, ef_search: 5 })
+        );
     }
 
     #[test]
@@ -0,0 +1,12 @@
+//! Shared helpers for integration tests
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Seeded random vectors with components in [-1, 1)
+pub fn random_vectors(n: usize, dimensions: usize, seed: u64) -> Vec<Vec<f32>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..n)
+        .map(|_| (0..dimensions).map(|_| rng.gen_range(-1.0..1.0)).collect())
+        .collect()
+}
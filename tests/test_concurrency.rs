@@ -9,9 +9,13 @@
 
 use omen::vector::types::Vector;
 use omen::vector::store::VectorStore;
+use omen::vector::{ConcurrentHNSWIndex, HNSWIndex};
 use std::sync::{Arc, Mutex};
 use std::thread;
 
+mod common;
+use common::random_vectors;
+
 /// Test parallel vector insertions
 #[test]
 fn test_parallel_insertions() {
@@ -479,3 +483,116 @@ fn test_concurrent_get_operations() {
         num_threads * gets_per_thread
     );
 }
+
+/// Stress test: concurrent HNSW searchers while one thread inserts
+#[test]
+fn test_concurrent_hnsw_search_during_inserts() {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    let dimensions = 32;
+    let initial = 2000;
+    let inserted = 1000;
+    let vectors = random_vectors(initial + inserted, dimensions, 7);
+
+    let index = Arc::new(ConcurrentHNSWIndex::new(HNSWIndex::new(10_000, dimensions)));
+    for v in &vectors[..initial] {
+        index.insert(v).unwrap();
+    }
+
+    let vectors = Arc::new(vectors);
+    let num_searchers = 8;
+    let queries_per_thread = 300;
+
+    let inserter = {
+        let index = Arc::clone(&index);
+        let vectors = Arc::clone(&vectors);
+        thread::spawn(move || {
+            for (i, v) in vectors[initial..].iter().enumerate() {
+                let id = index.insert(v).unwrap();
+                assert_eq!(id, initial + i, "IDs should stay sequential");
+            }
+        })
+    };
+
+    let searchers: Vec<_> = (0..num_searchers)
+        .map(|thread_id| {
+            let index = Arc::clone(&index);
+            let vectors = Arc::clone(&vectors);
+            thread::spawn(move || {
+                let mut local_rng = StdRng::seed_from_u64(thread_id as u64);
+                let mut found_self = 0;
+                for _ in 0..queries_per_thread {
+                    // Query with a stored vector: it should be its own nearest neighbor
+                    let target = local_rng.gen_range(0..initial);
+                    let results = index.search(&vectors[target], 10).unwrap();
+                    let len = index.len().unwrap();
+
+                    assert_eq!(results.len(), 10);
+                    for pair in results.windows(2) {
+                        assert!(pair[1].1 >= pair[0].1, "Results should be sorted by distance");
+                    }
+                    for &(id, _) in &results {
+                        assert!(id < len, "Result id {} beyond index size {}", id, len);
+                    }
+
+                    if results[0].0 == target {
+                        found_self += 1;
+                    }
+                }
+                found_self
+            })
+        })
+        .collect();
+
+    let found_self: usize = searchers.into_iter().map(|h| h.join().unwrap()).sum();
+    inserter.join().unwrap();
+
+    let recall = found_self as f64 / (num_searchers * queries_per_thread) as f64;
+    eprintln!("Self-recall under concurrent inserts: {:.2}%", recall * 100.0);
+    assert!(recall >= 0.95, "Recall dropped during inserts: {:.2}%", recall * 100.0);
+
+    // Every inserted vector is fully linked and reachable
+    assert_eq!(index.len().unwrap(), initial + inserted);
+    let mut missing = 0;
+    for (id, v) in vectors.iter().enumerate().skip(initial) {
+        if index.search(v, 1).unwrap()[0].0 != id {
+            missing += 1;
+        }
+    }
+    assert!(missing <= inserted / 20, "{} inserted vectors not found", missing);
+}
+
+/// Inserts through `write()` and `ConcurrentHNSWIndex::insert` can interleave
+#[test]
+fn test_concurrent_hnsw_mixed_write_guard_inserts() {
+    let dimensions = 16;
+    let per_thread = 300;
+    let index = Arc::new(ConcurrentHNSWIndex::new(HNSWIndex::new(10_000, dimensions)));
+
+    let handles: Vec<_> = (0..4u64)
+        .map(|thread_id| {
+            let index = Arc::clone(&index);
+            thread::spawn(move || {
+                for (i, v) in random_vectors(per_thread, dimensions, thread_id).iter().enumerate() {
+                    // Half the threads go through the raw write guard
+                    if thread_id % 2 == 0 && i % 2 == 0 {
+                        index.write().unwrap().insert(v).unwrap();
+                    } else {
+                        index.insert(v).unwrap();
+                    }
+                }
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(index.len().unwrap(), 4 * per_thread);
+    for v in random_vectors(per_thread, dimensions, 1).iter().take(20) {
+        let results = index.search(v, 1).unwrap();
+        assert_eq!(results[0].1, 0.0, "Inserted vector should be found exactly");
+    }
+}
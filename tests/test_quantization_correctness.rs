@@ -437,8 +437,9 @@ fn test_quantization_serialization() {
 /// Test high-dimensional quantization (1536D, OpenAI embedding size)
 #[test]
 fn test_quantization_high_dimensional() {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    let mut rng = StdRng::seed_from_u64(42);
 
     let dimensions = 1536;
     let num_vectors = 500;
@@ -479,51 +480,64 @@ fn test_quantization_high_dimensional() {
         compression_ratio
     );
 
-    // Test recall on high-dimensional data
-    let query_idx = 0;
-    let query = &vectors[query_idx];
-    let query_quantized = &quantized[query_idx];
+    // Test recall on high-dimensional data, averaged over several queries
+    let num_queries = 100;
+    let mut total_recall = 0.0f32;
 
-    // Ground truth
-    let mut l2_results: Vec<(usize, f32)> = vectors
-        .iter()
-        .enumerate()
-        .map(|(i, v)| {
-            let dist: f32 = query
-                .iter()
-                .zip(v.iter())
-                .map(|(a, b)| {
-                    let diff = a - b;
-                    diff * diff
-                })
-                .sum::<f32>()
-                .sqrt();
-            (i, dist)
-        })
-        .collect();
-    l2_results.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
-    let ground_truth: std::collections::HashSet<usize> =
-        l2_results.iter().take(k).map(|(id, _)| *id).collect();
+    for query_idx in 0..num_queries {
+        let query = &vectors[query_idx];
+        let query_quantized = &quantized[query_idx];
 
-    // Hamming distance
-    let mut hamming_results: Vec<(usize, u32)> = quantized
-        .iter()
-        .enumerate()
-        .map(|(i, qv)| (i, query_quantized.hamming_distance(qv)))
-        .collect();
-    hamming_results.sort_by(|a, b| a.1.cmp(&b.1));
-    let hamming_top_k: std::collections::HashSet<usize> =
-        hamming_results.iter().take(k).map(|(id, _)| *id).collect();
+        // Ground truth
+        let mut l2_results: Vec<(usize, f32)> = vectors
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let dist: f32 = query
+                    .iter()
+                    .zip(v.iter())
+                    .map(|(a, b)| {
+                        let diff = a - b;
+                        diff * diff
+                    })
+                    .sum::<f32>()
+                    .sqrt();
+                (i, dist)
+            })
+            .collect();
+        l2_results.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        let ground_truth: std::collections::HashSet<usize> =
+            l2_results.iter().take(k).map(|(id, _)| *id).collect();
+
+        // Hamming distance
+        let mut hamming_results: Vec<(usize, u32)> = quantized
+            .iter()
+            .enumerate()
+            .map(|(i, qv)| (i, query_quantized.hamming_distance(qv)))
+            .collect();
+        hamming_results.sort_by_key(|a| a.1);
+        let hamming_top_k: std::collections::HashSet<usize> =
+            hamming_results.iter().take(k).map(|(id, _)| *id).collect();
 
-    let recall = ground_truth.intersection(&hamming_top_k).count() as f32 / k as f32;
+        total_recall += ground_truth.intersection(&hamming_top_k).count() as f32 / k as f32;
+    }
+
+    let recall = total_recall / num_queries as f32;
 
-    eprintln!("High-dimensional recall@{}: {:.2}%", k, recall * 100.0);
+    eprintln!(
+        "High-dimensional recall@{} (avg over {} queries): {:.2}%",
+        k,
+        num_queries,
+        recall * 100.0
+    );
 
-    // High-dimensional binary quantization achieves 40-50% recall
-    // This is expected for 1-bit quantization on normalized embeddings
+    // 1-bit quantization of isotropic random vectors averages ~41% recall@10
+    // (zero thresholds score the same, so centering isn't the limit). Over
+    // 100 queries the average stays within a few points of that for any
+    // seed; 35% leaves room for that spread.
     assert!(
-        recall >= 0.40,
-        "High-dimensional recall too low: {:.2}% (expected >= 40%)",
+        recall >= 0.35,
+        "High-dimensional recall too low: {:.2}% (expected >= 35%)",
         recall * 100.0
     );
 